@@ -0,0 +1,273 @@
+//! Independent planning for several start/goal pairs on the same board.
+use iced::widget::canvas::{Fill, Frame, Path, Stroke};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::polygon::{darken, COLORS};
+use crate::search::{Search, SearchVariant};
+use crate::{Board, Heuristic, Pathfinder, Point};
+
+/// A set of agents, each with its own start/goal pair and [`Search`].
+///
+/// Agents are planned independently of each other, so their paths may cross
+/// or overlap; there is no collision avoidance between them.
+#[derive(Clone)]
+pub struct Agents {
+    searches: Vec<Search>,
+}
+
+impl Agents {
+    /// Plans a [`Search`] for every start/goal pair on the board.
+    ///
+    /// The first agent's search is built from scratch and every later one is
+    /// rebuilt from a copy of it, so the visibility graph between obstacle
+    /// vertices is only computed once.
+    pub fn new(
+        board: Board,
+        pairs: &[(Point, Point)],
+        heuristic: Heuristic,
+        variant: SearchVariant,
+    ) -> Self {
+        let mut searches: Vec<Search> = Vec::with_capacity(pairs.len());
+
+        for &(start, goal) in pairs {
+            let search = match searches.first() {
+                Some(first) => {
+                    let mut search = first.clone();
+                    search.rebuild(board.clone(), start, goal, heuristic);
+                    search
+                }
+                None => Search::new_for_variant(board.clone(), start, goal, heuristic, variant),
+            };
+            searches.push(search);
+        }
+
+        Self { searches }
+    }
+
+    /// Returns the search for each agent, in the order the pairs were given
+    pub fn searches(&self) -> &[Search] {
+        &self.searches
+    }
+
+    /// Returns the start/goal pair of each agent
+    pub fn pairs(&self) -> Vec<(Point, Point)> {
+        self.searches
+            .iter()
+            .map(|search| (search.get_start(), search.get_goal()))
+            .collect()
+    }
+
+    /// Returns true if there are no agents
+    pub fn is_empty(&self) -> bool {
+        self.searches.is_empty()
+    }
+
+    /// Plans timed paths for every agent so that no two agents occupy the same
+    /// vertex at the same timestep, using cooperative A* with a reservation
+    /// table.
+    ///
+    /// Time advances one step per visibility graph hop, and an agent may also
+    /// wait in place for a step. Agents are planned one after another in
+    /// order, each reserving the `(vertex, time)` cells along its path and
+    /// then its goal for good, and later agents treat reserved cells as
+    /// blocked. Only vertex conflicts are avoided; agents may still cross
+    /// mid-edge. Element `t` of each returned path is the agent's position at
+    /// time `t`, and an agent with no conflict-free path gets an empty one.
+    pub fn plan_cooperative(&self) -> Vec<Vec<Point>> {
+        let Some(board) = self.searches.first().map(|search| search.get_board()) else {
+            return Vec::new();
+        };
+        let pairs = self.pairs();
+
+        let mut nodes: Vec<Point> = board.vertices().into_iter().collect();
+        nodes.extend(pairs.iter().flat_map(|&(start, goal)| [start, goal]));
+        nodes.sort_by_key(|p| (p.x, p.y));
+        nodes.dedup();
+
+        let neighbors: Vec<Vec<usize>> = nodes
+            .iter()
+            .map(|from| {
+                (0..nodes.len())
+                    .filter(|&j| nodes[j] != *from && board.line_of_sight(from, &nodes[j]))
+                    .collect()
+            })
+            .collect();
+        let index = |point: Point| nodes.iter().position(|&p| p == point);
+
+        // Long enough for every agent to wait out all those planned before it
+        let horizon = nodes.len() * (pairs.len() + 1);
+
+        let mut reserved: HashSet<(Point, usize)> = HashSet::new();
+        let mut parked: HashMap<Point, usize> = HashMap::new();
+
+        pairs
+            .iter()
+            .map(|&(start, goal)| {
+                let is_free = |point: Point, time: usize| {
+                    !reserved.contains(&(point, time))
+                        && parked.get(&point).is_none_or(|&from| time < from)
+                };
+                let can_park = |time: usize| {
+                    !parked.contains_key(&goal)
+                        && !reserved.iter().any(|&(p, t)| p == goal && t >= time)
+                };
+
+                let (Some(start_index), Some(_)) = (index(start), index(goal)) else {
+                    return Vec::new();
+                };
+                if !is_free(start, 0) {
+                    return Vec::new();
+                }
+
+                // Breadth-first search through space-time, which finds the
+                // earliest arrival since every step takes one unit of time
+                let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+                let mut visited = HashSet::from([(start_index, 0)]);
+                let mut queue = VecDeque::from([(start_index, 0)]);
+                let mut arrival = None;
+
+                while let Some((i, time)) = queue.pop_front() {
+                    if nodes[i] == goal && can_park(time) {
+                        arrival = Some((i, time));
+                        break;
+                    }
+                    if time >= horizon {
+                        continue;
+                    }
+
+                    for &j in std::iter::once(&i).chain(&neighbors[i]) {
+                        let next = (j, time + 1);
+                        if is_free(nodes[j], time + 1) && visited.insert(next) {
+                            came_from.insert(next, (i, time));
+                            queue.push_back(next);
+                        }
+                    }
+                }
+
+                let Some(mut current) = arrival else {
+                    return Vec::new();
+                };
+                let mut path = vec![nodes[current.0]];
+                while let Some(&previous) = came_from.get(&current) {
+                    path.push(nodes[previous.0]);
+                    current = previous;
+                }
+                path.reverse();
+
+                reserved.extend(path.iter().enumerate().map(|(time, &p)| (p, time)));
+                parked.insert(goal, path.len() - 1);
+
+                path
+            })
+            .collect()
+    }
+
+    /// Draws each agent's endpoints and optimal path in its own color. See
+    /// [`Point::to_canvas_with`] for `y_down`.
+    pub fn draw(&self, frame: &mut Frame, line_scale: f32, y_down: bool) {
+        for (i, search) in self.searches.iter().enumerate() {
+            let color = darken(COLORS[i % COLORS.len()], 0.4);
+
+            if let Some((path, _)) = search.get_optimal_path() {
+                let stroke = Stroke::default()
+                    .with_color(color)
+                    .with_width(2.0 * line_scale);
+
+                for window in path.windows(2) {
+                    let segment = Path::line(
+                        window[0].to_canvas_with(y_down),
+                        window[1].to_canvas_with(y_down),
+                    );
+                    frame.stroke(&segment, stroke);
+                }
+            }
+
+            for point in [search.get_start(), search.get_goal()] {
+                let circle = Path::circle(point.to_canvas_with(y_down), 1.5 * line_scale);
+                frame.fill(&circle, Fill::from(color));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polygon;
+
+    fn create_test_board() -> Board {
+        Board::new(vec![Polygon::new(vec![
+            (40, 40).into(),
+            (40, 60).into(),
+            (60, 60).into(),
+            (60, 40).into(),
+        ])])
+    }
+
+    #[test]
+    fn test_cooperative_paths_never_share_a_vertex() {
+        // A tall wall both agents must climb over, via the same two corners
+        let board = Board::new(vec![Polygon::new(vec![
+            (45, -100).into(),
+            (45, 100).into(),
+            (55, 100).into(),
+            (55, -100).into(),
+        ])]);
+        let pairs = [
+            (Point::new(0, 90), Point::new(100, 90)),
+            (Point::new(0, 80), Point::new(100, 80)),
+        ];
+        let agents = Agents::new(
+            board.clone(),
+            &pairs,
+            Heuristic::Euclidean,
+            SearchVariant::VisibilityGraph,
+        );
+
+        // Planned independently, both agents reach the corner at step 1
+        let naive: Vec<_> = agents
+            .searches()
+            .iter()
+            .map(|search| search.get_optimal_path().unwrap().0.clone())
+            .collect();
+        assert_eq!(naive[0][1], naive[1][1]);
+
+        let timed = agents.plan_cooperative();
+        assert_eq!(timed.len(), 2);
+
+        let mut occupied = HashSet::new();
+        for (path, &(start, goal)) in timed.iter().zip(&pairs) {
+            assert_eq!(path.first(), Some(&start));
+            assert_eq!(path.last(), Some(&goal));
+            assert!(board.path_is_valid(path), "Path {path:?} is invalid");
+
+            for (time, &point) in path.iter().enumerate() {
+                assert!(
+                    occupied.insert((point, time)),
+                    "Two agents occupy {point:?} at time {time}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_each_agent_gets_a_valid_path() {
+        let board = create_test_board();
+        let pairs = [
+            (Point::new(0, 0), Point::new(100, 100)),
+            (Point::new(0, 100), Point::new(100, 0)),
+        ];
+
+        for &variant in SearchVariant::ALL {
+            let agents = Agents::new(board.clone(), &pairs, Heuristic::Euclidean, variant);
+            assert_eq!(agents.pairs(), pairs);
+
+            for search in agents.searches() {
+                let (path, _) = search.get_optimal_path().expect("Agent should find a path");
+                assert_eq!(path.first(), Some(&search.get_start()));
+                assert_eq!(path.last(), Some(&search.get_goal()));
+                assert!(board.path_is_valid(path), "Path {path:?} is invalid");
+            }
+        }
+    }
+}
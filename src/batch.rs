@@ -0,0 +1,210 @@
+//! Headless runs of one search configuration over many boards, collecting
+//! metrics for empirical comparisons.
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::search::SearchVariant;
+use crate::{Board, Heuristic, LandmarkStrategy, Pathfinder, Search};
+
+/// Name of the file written to the working directory by [`run_dir`]
+pub const METRICS_FILE: &str = "metrics.csv";
+
+/// How a single search on one board went, once run to completion
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchMetrics {
+    /// Whether the search reached the goal
+    pub found: bool,
+    /// Cost of the optimal path, if one was found
+    pub cost: Option<i32>,
+    /// Number of vertices on the optimal path, 0 if none was found
+    pub path_vertices: usize,
+    /// Total steps in the search history
+    pub steps: usize,
+    /// Number of vertices closed by the final step
+    pub expanded: usize,
+    /// Wall-clock time to build and run the search
+    pub elapsed_ms: f64,
+}
+
+impl SearchMetrics {
+    /// Column names matching [`SearchMetrics::csv_row`], after a leading
+    /// board column
+    pub const CSV_HEADER: &'static str = "board,found,cost,path_vertices,steps,expanded,elapsed_ms";
+
+    /// Formats the metrics as one CSV line for the named board
+    pub fn csv_row(&self, board: &str) -> String {
+        format!(
+            "{board},{},{},{},{},{},{:.3}",
+            self.found,
+            self.cost.map_or_else(String::new, |cost| cost.to_string()),
+            self.path_vertices,
+            self.steps,
+            self.expanded,
+            self.elapsed_ms
+        )
+    }
+}
+
+/// Runs the search variant with the given heuristic on every board, between
+/// each board's [`Board::default_endpoints`], returning one set of metrics
+/// per board in the same order
+pub fn run_batch(
+    boards: &[Board],
+    variant: SearchVariant,
+    heuristic: Heuristic,
+) -> Vec<SearchMetrics> {
+    boards
+        .iter()
+        .map(|board| {
+            let (start, goal) = board.default_endpoints();
+            let timer = Instant::now();
+            let mut search =
+                Search::new_for_variant(board.clone(), start, goal, heuristic, variant);
+            let elapsed_ms = timer.elapsed().as_secs_f64() * 1000.0;
+
+            search.jump_to(search.total_steps());
+            let path = search.get_optimal_path();
+
+            SearchMetrics {
+                found: search.found_path(),
+                cost: path.map(|(_, cost)| *cost),
+                path_vertices: path.map_or(0, |(path, _)| path.len()),
+                steps: search.total_steps(),
+                expanded: search.get_state().closed.len(),
+                elapsed_ms,
+            }
+        })
+        .collect()
+}
+
+/// Writes named metrics as CSV, with a header line
+pub fn write_csv(rows: &[(String, SearchMetrics)], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "{}", SearchMetrics::CSV_HEADER)?;
+    for (name, metrics) in rows {
+        writeln!(out, "{}", metrics.csv_row(name))?;
+    }
+    Ok(())
+}
+
+/// Loads every `*.json` board in `dir`, in file name order, runs them with
+/// [`run_batch`] and writes the results to [`METRICS_FILE`]
+pub fn run_dir(dir: &Path, variant: SearchVariant, heuristic: Heuristic) -> io::Result<()> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+    paths.sort();
+
+    let boards = paths
+        .iter()
+        .map(|path| {
+            Board::from_json(&std::fs::read_to_string(path)?).map_err(|error| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {error}", path.display()),
+                )
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let rows: Vec<_> = paths
+        .iter()
+        .map(|path| {
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .zip(run_batch(&boards, variant, heuristic))
+        .collect();
+
+    let mut file = io::BufWriter::new(std::fs::File::create(METRICS_FILE)?);
+    write_csv(&rows, &mut file)?;
+    file.flush()
+}
+
+/// Runs `batch <dir> [visibility|astar] [euclidean|manhattan|landmarks[:N]]`
+/// from the command line arguments following the subcommand, where `N` is the
+/// number of landmarks, 4 by default. Only the visibility graph search
+/// takes landmarks.
+pub fn run_cli(args: &[String]) -> Result<(), String> {
+    const USAGE: &str =
+        "usage: pathfinder batch <dir> [visibility|astar] [euclidean|manhattan|landmarks[:N]]";
+
+    let [dir, options @ ..] = args else {
+        return Err(USAGE.to_string());
+    };
+
+    let (mut variant, mut heuristic) = (SearchVariant::VisibilityGraph, Heuristic::Euclidean);
+    for option in options {
+        match option.as_str() {
+            "visibility" => variant = SearchVariant::VisibilityGraph,
+            "astar" => variant = SearchVariant::AStar,
+            "euclidean" => heuristic = Heuristic::Euclidean,
+            "manhattan" => heuristic = Heuristic::Manhattan,
+            "landmarks" => heuristic = Heuristic::landmarks(4, LandmarkStrategy::Farthest),
+            other if other.starts_with("landmarks:") => {
+                let count = other["landmarks:".len()..]
+                    .parse()
+                    .map_err(|_| format!("invalid landmark count in `{other}`\n{USAGE}"))?;
+                heuristic = Heuristic::landmarks(count, LandmarkStrategy::Farthest);
+            }
+            other => return Err(format!("unknown option `{other}`\n{USAGE}")),
+        }
+    }
+    if !variant.supports(heuristic) {
+        return Err(format!(
+            "{variant} can't use the {heuristic} heuristic\n{USAGE}"
+        ));
+    }
+
+    run_dir(Path::new(dir), variant, heuristic).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::sample_board;
+
+    #[test]
+    fn test_run_batch() {
+        let boards = [sample_board(), Board::from_grid("#.\n..", 50)];
+        let metrics = run_batch(&boards, SearchVariant::AStar, Heuristic::Euclidean);
+
+        assert_eq!(metrics.len(), 2);
+        assert!(metrics.iter().all(|m| m.found && m.cost.is_some()));
+
+        let rows: Vec<_> = ["a.json", "b.json"]
+            .into_iter()
+            .map(String::from)
+            .zip(metrics)
+            .collect();
+        let mut out = Vec::new();
+        write_csv(&rows, &mut out).unwrap();
+
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], SearchMetrics::CSV_HEADER);
+        assert!(lines[1].starts_with("a.json,true,"));
+    }
+
+    #[test]
+    fn test_run_cli_rejects_unsupported_heuristic() {
+        let args = |options: &[&str]| -> Vec<String> {
+            std::iter::once("missing-dir")
+                .chain(options.iter().copied())
+                .map(String::from)
+                .collect()
+        };
+
+        let error = run_cli(&args(&["astar", "landmarks"])).unwrap_err();
+        assert!(error.starts_with("A* can't use"), "{error}");
+        assert!(error.ends_with("[euclidean|manhattan|landmarks[:N]]"));
+
+        // Supported pairs get as far as reading the directory
+        let error = run_cli(&args(&["visibility", "landmarks:2"])).unwrap_err();
+        assert!(!error.contains("usage"), "{error}");
+    }
+}
@@ -1,14 +1,61 @@
 use iced::widget::canvas::{Fill, Frame, Path, Stroke, Text};
 use iced::Color;
+use std::cmp::Ordering;
 use std::collections::HashSet;
 
-use crate::{Edge, Point, Polygon};
+use crate::rng::SplitMix64;
+use crate::{Coordinate, DrawOptions, Edge, Point, Polygon};
+
+mod json;
+
+pub use json::BoardError;
+pub(crate) use json::{document_version, BoardV1};
+
+/// Summary metrics describing a [`Board`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardStats {
+    pub polygon_count: usize,
+    pub vertex_count: usize,
+    pub avg_vertices_per_polygon: f64,
+    /// Total area covered by obstacles
+    pub obstacle_area: f64,
+    /// Fraction of the board's bounding box covered by obstacles
+    pub coverage: f64,
+}
+
+impl std::fmt::Display for BoardStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} polygons, {} vertices ({:.1} avg), area {:.0} ({:.1}% coverage)",
+            self.polygon_count,
+            self.vertex_count,
+            self.avg_vertices_per_polygon,
+            self.obstacle_area,
+            self.coverage * 100.0
+        )
+    }
+}
+
+/// Polygons that differ between two [`Board`]s, as returned by [`Board::diff`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardDiff<T = i32> {
+    /// Polygons only on the other board
+    pub added: Vec<Polygon<T>>,
+    /// Polygons only on this board
+    pub removed: Vec<Polygon<T>>,
+}
+
+/// Distance between the points [`Board::gap_clearance`] samples along a segment
+const GAP_SAMPLE_SPACING: f64 = 2.0;
 
 /// Represents the game board containing polygonal obstacles
-#[derive(Clone, Debug)]
-pub struct Board {
+#[derive(Clone, Debug, PartialEq)]
+pub struct Board<T = i32> {
     /// The collection of polygon obstacles
-    polygons: Vec<Polygon>,
+    polygons: Vec<Polygon<T>>,
+    /// Indices of polygons that are drawn but ignored by collision checks
+    disabled: HashSet<usize>,
 }
 
 impl Default for Board {
@@ -17,127 +64,707 @@ impl Default for Board {
     }
 }
 
-impl Board {
+impl<T: Coordinate> Board<T> {
     /// Creates a new board with the given polygons, start point, and goal point
-    pub fn new(polygons: Vec<Polygon>) -> Self {
-        Self { polygons }
+    pub fn new(polygons: Vec<Polygon<T>>) -> Self {
+        Self {
+            polygons,
+            disabled: HashSet::new(),
+        }
     }
 
-    /// Returns an iterator over the polygons on the board
-    pub fn polygons(&self) -> impl Iterator<Item = &Polygon> {
+    /// Returns an iterator over the polygons on the board, including disabled
+    /// ones
+    pub fn polygons(&self) -> impl Iterator<Item = &Polygon<T>> {
         self.polygons.iter()
     }
 
-    /// Returns all vertices from all polygons
-    pub fn vertices(&self) -> HashSet<Point<i32>> {
+    /// Returns an iterator over the polygons that block movement, i.e. all
+    /// but the disabled ones
+    pub fn enabled_polygons(&self) -> impl Iterator<Item = &Polygon<T>> {
+        self.enabled().map(|(_, polygon)| polygon)
+    }
+
+    /// Enabled polygons along with their index among all polygons
+    fn enabled(&self) -> impl Iterator<Item = (usize, &Polygon<T>)> {
+        self.polygons
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !self.disabled.contains(i))
+    }
+
+    /// Returns true unless the polygon at `index` was disabled
+    pub fn is_polygon_enabled(&self, index: usize) -> bool {
+        !self.disabled.contains(&index)
+    }
+
+    /// Enables or disables the polygon at `index`. Disabled polygons are still
+    /// drawn, greyed out, but paths may cross them and their vertices aren't
+    /// waypoints.
+    pub fn set_polygon_enabled(&mut self, index: usize, enabled: bool) {
+        if enabled {
+            self.disabled.remove(&index);
+        } else if index < self.polygons.len() {
+            self.disabled.insert(index);
+        }
+    }
+
+    /// Returns all vertices from all enabled polygons
+    pub fn vertices(&self) -> HashSet<Point<T>> {
         let mut vertices = HashSet::new();
-        for polygon in &self.polygons {
+        for polygon in self.enabled_polygons() {
             vertices.extend(polygon.vertices_vec());
         }
         vertices
     }
 
-    /// Returns all outer edges from all polygons
-    pub fn outer_edges(&self) -> Vec<Edge> {
-        self.polygons().flat_map(|p| p.outer_edges()).collect()
+    /// Returns the vertices of all enabled polygons sorted by `(x, y)`, with
+    /// vertices shared between polygons listed once. Unlike
+    /// [`Board::vertices`], the order is deterministic.
+    pub fn vertices_ordered(&self) -> Vec<Point<T>> {
+        let mut vertices: Vec<_> = self
+            .enabled_polygons()
+            .flat_map(|polygon| polygon.vertices().copied())
+            .collect();
+        vertices.sort_by(|a, b| {
+            (a.x, a.y)
+                .partial_cmp(&(b.x, b.y))
+                .unwrap_or(Ordering::Equal)
+        });
+        vertices.dedup();
+        vertices
+    }
+
+    /// Returns an equivalent board with each polygon in its
+    /// [`Polygon::canonical_form`] and the polygons sorted by their vertices,
+    /// so boards listing the same obstacles in any order compare equal with
+    /// [`Board::diff`] or by their polygons. Disabled polygons stay disabled.
+    pub fn canonical_form(&self) -> Self {
+        let by = |a: &Point<T>, b: &Point<T>| {
+            let by = |a: T, b: T| a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            by(a.x, b.x).then(by(a.y, b.y))
+        };
+
+        let mut polygons: Vec<_> = self
+            .polygons
+            .iter()
+            .enumerate()
+            .map(|(i, polygon)| (polygon.canonical_form(), self.disabled.contains(&i)))
+            .collect();
+        polygons.sort_by(|(a, _), (b, _)| {
+            a.vertices()
+                .zip(b.vertices())
+                .map(|(a, b)| by(a, b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(a.vertices().count().cmp(&b.vertices().count()))
+        });
+
+        Self {
+            disabled: (0..polygons.len()).filter(|&i| polygons[i].1).collect(),
+            polygons: polygons.into_iter().map(|(polygon, _)| polygon).collect(),
+        }
+    }
+
+    /// Returns a copy of the board with every polygon scaled by `factor`
+    /// about a [`Point`]. Use [`Point::scaled`] with the same arguments to
+    /// keep start and goal in place relative to the obstacles.
+    pub fn scaled(&self, factor: f32, about: Point<T>) -> Self {
+        Self {
+            polygons: self
+                .polygons
+                .iter()
+                .map(|p| p.scaled(factor, about))
+                .collect(),
+            disabled: self.disabled.clone(),
+        }
+    }
+
+    /// Returns a copy of the board mirrored left to right across the y axis,
+    /// negating every x coordinate
+    pub fn reflected_x(&self) -> Self {
+        self.transformed(|v| Point::new(T::zero() - v.x, v.y))
+    }
+
+    /// Returns a copy of the board mirrored top to bottom across the x axis,
+    /// negating every y coordinate
+    pub fn reflected_y(&self) -> Self {
+        self.transformed(|v| Point::new(v.x, T::zero() - v.y))
+    }
+
+    /// Returns a copy of the board rotated a quarter turn counterclockwise
+    /// about the origin. The transforms compose, e.g. two rotations equal
+    /// reflecting across both axes.
+    pub fn rotated_90(&self) -> Self {
+        self.transformed(|v| Point::new(T::zero() - v.y, v.x))
+    }
+
+    /// Applies a transform to every polygon's vertices
+    fn transformed(&self, transform: impl Fn(Point<T>) -> Point<T>) -> Self {
+        Self {
+            polygons: self
+                .polygons
+                .iter()
+                .map(|p| Polygon::new(p.vertices().map(|&v| transform(v)).collect()))
+                .collect(),
+            disabled: self.disabled.clone(),
+        }
+    }
+
+    /// Removes every polygon whose bounding box lies fully inside the
+    /// rectangle spanned by `min` and `max`, e.g. from a drag selection
+    pub fn remove_polygons_in_rect(&mut self, min: Point<T>, max: Point<T>) {
+        for index in (0..self.polygons.len()).rev() {
+            let (lo, hi) = self.polygons[index].bounding_box();
+            let inside = lo.x >= min.x && lo.y >= min.y && hi.x <= max.x && hi.y <= max.y;
+            if inside {
+                self.remove_polygon(index);
+            }
+        }
+    }
+
+    /// Merges axis-aligned rectangular obstacles that share a full edge into
+    /// larger rectangles, e.g. the unit squares from [`Board::from_grid`].
+    ///
+    /// Rectangles are first joined into horizontal strips, then strips of
+    /// equal extent are stacked vertically. Every merge replaces two
+    /// rectangles with exactly their union, so the covered area is unchanged.
+    /// Other polygons, and disabled rectangles, are kept as they are, ahead of
+    /// the merged rectangles.
+    pub fn merge_axis_aligned_rectangles(&mut self) {
+        let mut rects = Vec::new();
+        let mut kept = Vec::new();
+        let mut disabled = HashSet::new();
+        for (i, polygon) in std::mem::take(&mut self.polygons).into_iter().enumerate() {
+            match as_rectangle(&polygon) {
+                Some(rect) if !self.disabled.contains(&i) => rects.push(rect),
+                _ => {
+                    if self.disabled.contains(&i) {
+                        disabled.insert(kept.len());
+                    }
+                    kept.push(polygon);
+                }
+            }
+        }
+        self.polygons = kept;
+        self.disabled = disabled;
+
+        merge_rectangles(&mut rects, |a, b| {
+            a.0.y == b.0.y && a.1.y == b.1.y && (a.1.x == b.0.x || b.1.x == a.0.x)
+        });
+        merge_rectangles(&mut rects, |a, b| {
+            a.0.x == b.0.x && a.1.x == b.1.x && (a.1.y == b.0.y || b.1.y == a.0.y)
+        });
+
+        self.polygons.extend(rects.into_iter().map(|(min, max)| {
+            Polygon::new(vec![
+                min,
+                Point::new(min.x, max.y),
+                max,
+                Point::new(max.x, min.y),
+            ])
+        }));
+    }
+
+    /// Subdivides every polygon's edges to at most `max_edge_len`, as far as
+    /// possible without changing their shape. See [`Polygon::densified`].
+    pub fn densify_all(&mut self, max_edge_len: f64) {
+        for polygon in &mut self.polygons {
+            *polygon = polygon.densified(max_edge_len);
+        }
+    }
+
+    /// Drops every polygon's vertices within `epsilon` of its simplified
+    /// outline, e.g. to speed up searches on imported boards. See
+    /// [`Polygon::simplified`].
+    pub fn simplify_all(&mut self, epsilon: f64) {
+        for polygon in &mut self.polygons {
+            *polygon = polygon.simplified(epsilon);
+        }
+    }
+
+    /// Compares this board's polygons to another's, regardless of order.
+    /// Duplicates are matched one to one, so a polygon present twice on the
+    /// other board and once here counts as added once.
+    pub fn diff(&self, other: &Board<T>) -> BoardDiff<T> {
+        let mut removed: Vec<_> = self.polygons.clone();
+        let mut added = Vec::new();
+
+        for polygon in &other.polygons {
+            match removed.iter().position(|p| p == polygon) {
+                Some(index) => {
+                    removed.remove(index);
+                }
+                None => added.push(polygon.clone()),
+            }
+        }
+
+        BoardDiff { added, removed }
+    }
+
+    /// Returns all outer edges from all enabled polygons
+    pub fn outer_edges(&self) -> Vec<Edge<T>> {
+        self.enabled_polygons()
+            .flat_map(|p| p.outer_edges())
+            .collect()
+    }
+
+    /// Lazily yields all outer edges from all enabled polygons, in the same
+    /// order as [`Board::outer_edges`], without allocating
+    pub fn edges_iter(&self) -> impl Iterator<Item = Edge<T>> + '_ {
+        self.enabled_polygons().flat_map(|p| p.edges_iter())
+    }
+
+    /// Returns true if the segment between two points crosses no enabled
+    /// obstacle
+    pub fn line_of_sight(&self, from: &Point<T>, to: &Point<T>) -> bool {
+        self.enabled_polygons()
+            .all(|p| !p.intersects_segment(from, to))
+    }
+
+    /// Returns true if two distinct points see each other as the visibility
+    /// graph connects them, which is [`Board::line_of_sight`] except that a
+    /// polygon's own sides are always walkable
+    pub fn vertices_visible(&self, v1: &Point<T>, v2: &Point<T>) -> bool {
+        if v1 == v2 {
+            return false;
+        }
+
+        for polygon in self.enabled_polygons() {
+            // A polygon's own sides are always walkable, though another
+            // polygon may still cross them
+            let vertices = polygon.vertices_vec();
+            let n = vertices.len();
+            let is_side = (0..n).any(|i| {
+                let j = (i + 1) % n;
+                (vertices[i] == *v1 && vertices[j] == *v2)
+                    || (vertices[i] == *v2 && vertices[j] == *v1)
+            });
+            if is_side {
+                continue;
+            }
+
+            // Everything else is checked geometrically, including chords
+            // between two of this polygon's vertices, so that a start or goal
+            // placed on a vertex still sees across a concave notch
+            if polygon.intersects_segment(v1, v2) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns the index of the polygon containing a point, if any. Points on
+    /// a polygon's boundary aren't inside it.
+    pub fn polygon_at(&self, point: &Point<T>) -> Option<usize> {
+        self.polygons().position(|p| p.contains_point(point))
     }
 
-    /// Draw the board on the given frame. Note that y-coordinates are flipped
-    /// to match mathematical coordinates.
-    pub fn draw(&self, frame: &mut Frame) {
+    /// Adds a polygon on top of the existing ones
+    pub fn add_polygon(&mut self, polygon: Polygon<T>) {
+        self.polygons.push(polygon);
+    }
+
+    /// Adds a polygon on top of the existing ones if it is simple, has at least
+    /// three vertices and doesn't overlap any of them
+    pub fn try_add_polygon(&mut self, polygon: Polygon<T>) -> Result<(), BoardError> {
+        let count = polygon.vertices().count();
+        if count < 3 {
+            return Err(BoardError::TooFewVertices(count));
+        }
+        if !polygon.is_simple() {
+            return Err(BoardError::SelfIntersecting);
+        }
+        if let Some(index) = self.polygons().position(|p| p.overlaps(&polygon)) {
+            return Err(BoardError::Overlapping(index));
+        }
+
+        self.add_polygon(polygon);
+        Ok(())
+    }
+
+    /// Removes and returns the polygon at `index`, shifting later ones down
+    pub fn remove_polygon(&mut self, index: usize) -> Polygon<T> {
+        self.disabled = self
+            .disabled
+            .iter()
+            .filter(|&&i| i != index)
+            .map(|&i| if i > index { i - 1 } else { i })
+            .collect();
+        self.polygons.remove(index)
+    }
+
+    /// Returns the index of the first enabled polygon blocking the segment
+    /// between two points, or `None` if there is line of sight between them
+    pub fn blocking_polygon(&self, from: &Point<T>, to: &Point<T>) -> Option<usize> {
+        self.enabled()
+            .find(|(_, p)| p.intersects_segment(from, to))
+            .map(|(i, _)| i)
+    }
+
+    /// Returns the distance between the segment from one point to another and
+    /// the nearest obstacle, which is infinite on an empty board.
+    ///
+    /// Unless the segment crosses an obstacle, that distance is reached at
+    /// one of its endpoints or at an obstacle vertex, so checking those is exact.
+    pub fn segment_clearance(&self, from: &Point<T>, to: &Point<T>) -> f64 {
+        let segment = Edge::new(*from, *to);
+        let at_endpoints = [from, to]
+            .into_iter()
+            .filter_map(|p| self.closest_obstacle(p))
+            .map(|(_, distance)| distance);
+        let at_vertices = self
+            .enabled_polygons()
+            .flat_map(|p| p.vertices())
+            .map(|v| segment.distance_to_point(v));
+
+        at_endpoints
+            .chain(at_vertices)
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Returns how much room the segment from one point to another has in the
+    /// tightest gap between two enabled obstacles it passes through, which is
+    /// infinite on a board with fewer than two.
+    ///
+    /// The room at a point is its distance to the second-nearest obstacle,
+    /// e.g. half the gap's width at its middle, taken at points a couple of
+    /// units apart along the segment. Unlike [`Board::segment_clearance`],
+    /// this ignores a single obstacle the segment runs along, which every
+    /// segment between obstacle vertices does, and only sees how tightly it
+    /// squeezes between two of them.
+    pub fn gap_clearance(&self, from: &Point<T>, to: &Point<T>) -> f64 {
+        let (x0, y0, x1, y1): (f64, f64, f64, f64) =
+            (from.x.as_(), from.y.as_(), to.x.as_(), to.y.as_());
+        let samples = ((x1 - x0).hypot(y1 - y0) / GAP_SAMPLE_SPACING)
+            .ceil()
+            .max(1.0) as usize;
+
+        (0..=samples)
+            .map(|i| {
+                let t = i as f64 / samples as f64;
+                let point = Point::new(
+                    T::round_f64(x0 + (x1 - x0) * t),
+                    T::round_f64(y0 + (y1 - y0) * t),
+                );
+                let (mut nearest, mut second) = (f64::INFINITY, f64::INFINITY);
+                for distance in self.enabled_polygons().map(|p| p.distance_to_point(&point)) {
+                    if distance < nearest {
+                        (nearest, second) = (distance, nearest);
+                    } else if distance < second {
+                        second = distance;
+                    }
+                }
+                second
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Returns the indices of the path's segments that pass closer than
+    /// `clearance` to an obstacle, e.g. half a robot's width
+    pub fn narrow_segments(&self, path: &[Point<T>], clearance: f64) -> Vec<usize> {
+        path.windows(2)
+            .enumerate()
+            .filter(|(_, w)| self.segment_clearance(&w[0], &w[1]) < clearance)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the index of the enabled polygon nearest to a point along with
+    /// the distance to it, which is 0.0 if the point is inside it, or `None`
+    /// for a board without enabled obstacles
+    pub fn closest_obstacle(&self, point: &Point<T>) -> Option<(usize, f64)> {
+        self.enabled()
+            .map(|(i, polygon)| (i, polygon.distance_to_point(point)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+
+    /// Returns true if the point lies strictly inside any enabled obstacle
+    pub fn contains_point(&self, point: &Point<T>) -> bool {
+        self.enabled_polygons().any(|p| p.contains_point(point))
+    }
+
+    /// Returns true if some path around the enabled obstacles connects two
+    /// points, neither of which may lie inside an obstacle.
+    ///
+    /// This is [`Board::same_free_region`], which only checks connectivity
+    /// without tracking costs, so it is cheaper than finding the path but
+    /// still checks many sightlines on large boards.
+    pub fn is_reachable(&self, from: &Point<T>, to: &Point<T>) -> bool {
+        self.same_free_region(from, to)
+    }
+
+    /// Labels the connected regions of free space the points lie in, over the
+    /// visibility graph of obstacle vertices plus the points themselves, or
+    /// `None` for a point inside an obstacle.
+    ///
+    /// This tells apart "no path exists" from "the search found nothing".
+    /// Labels are only comparable within one call: a point that sees no
+    /// obstacle vertex, e.g. inside a pocket whose corners are all crossings
+    /// of overlapping obstacles, shares a label only with the other points
+    /// it sees.
+    pub fn free_space_components(&self, points: &[Point<T>]) -> Vec<Option<usize>> {
+        let nodes: Vec<Point<T>> = points
+            .iter()
+            .copied()
+            .chain(self.vertices_ordered())
+            .collect();
+        let free: Vec<bool> = nodes
+            .iter()
+            .map(|node| !self.contains_point(node))
+            .collect();
+        let mut labels = vec![None; nodes.len()];
+        let mut next = 0;
+
+        for seed in 0..nodes.len() {
+            if !free[seed] || labels[seed].is_some() {
+                continue;
+            }
+            labels[seed] = Some(next);
+            let mut stack = vec![seed];
+            while let Some(i) = stack.pop() {
+                for j in 0..nodes.len() {
+                    if free[j] && labels[j].is_none() && self.line_of_sight(&nodes[i], &nodes[j]) {
+                        labels[j] = Some(next);
+                        stack.push(j);
+                    }
+                }
+            }
+            next += 1;
+        }
+
+        labels.truncate(points.len());
+        labels
+    }
+
+    /// Returns true if both points are outside every obstacle and connected
+    /// in the visibility graph of obstacle vertices plus the two points
+    pub fn same_free_region(&self, a: &Point<T>, b: &Point<T>) -> bool {
+        match self.free_space_components(&[*a, *b])[..] {
+            [Some(a), Some(b)] => a == b,
+            _ => false,
+        }
+    }
+
+    /// Returns true if every segment of the path has line of sight and no
+    /// vertex of the path lies inside an obstacle
+    pub fn path_is_valid(&self, path: &[Point<T>]) -> bool {
+        let endpoints_free = path.iter().all(|point| !self.contains_point(point));
+
+        endpoints_free
+            && path
+                .windows(2)
+                .all(|window| self.line_of_sight(&window[0], &window[1]))
+    }
+
+    /// Draw the board on the given frame, with stroke widths multiplied by
+    /// the options' `line_scale`, obstacles filled using their `fill_rule`
+    /// and, if `show_axes` is set, tick marks and coordinate labels along the
+    /// boundary. Note that y-coordinates are flipped to match mathematical
+    /// coordinates unless `y_down` is set. Other options are ignored.
+    pub fn draw(&self, frame: &mut Frame, options: DrawOptions) {
+        self.draw_where(frame, options, |_| true);
+    }
+
+    /// Draw the board like [`Board::draw`], but only reveal polygons with a
+    /// vertex within `radius` of an explored point, hiding the rest under fog
+    pub fn draw_revealed(
+        &self,
+        frame: &mut Frame,
+        options: DrawOptions,
+        explored: &[Point<T>],
+        radius: f64,
+    ) {
+        self.draw_where(frame, options, |p| p.is_near(explored, radius));
+    }
+
+    fn draw_where(
+        &self,
+        frame: &mut Frame,
+        options: DrawOptions,
+        reveal: impl Fn(&Polygon<T>) -> bool,
+    ) {
+        let DrawOptions {
+            line_scale,
+            show_axes,
+            fill_rule,
+            y_down,
+            ..
+        } = options;
+
         // Determine the bounds of the board by finding min/max coordinates of polygons
         let (min_x, min_y, max_x, max_y) = self.bounds();
+        let (min_x, min_y, max_x, max_y): (f32, f32, f32, f32) =
+            (min_x.as_(), min_y.as_(), max_x.as_(), max_y.as_());
+        let flip = |y: f32| if y_down { y } else { -y };
+        // Whichever edge ends up at the top of the canvas
+        let top = if y_down { min_y } else { -max_y };
 
         // Draw the white background
-        let background = Path::rectangle(
-            (min_x as f32, -max_y as f32).into(), // Flip y-coordinate
-            (max_x as f32 - min_x as f32, (max_y - min_y) as f32).into(),
-        );
+        let background =
+            Path::rectangle((min_x, top).into(), (max_x - min_x, max_y - min_y).into());
         frame.fill(&background, Fill::from(Color::WHITE));
 
         // Draw the boundary square around the board
-        let boundary = Path::rectangle(
-            (min_x as f32, -max_y as f32).into(), // Flip y-coordinate
-            (max_x as f32 - min_x as f32, (max_y - min_y) as f32).into(),
-        );
+        let boundary = Path::rectangle((min_x, top).into(), (max_x - min_x, max_y - min_y).into());
         frame.stroke(
             &boundary,
-            Stroke::default().with_color(Color::BLACK).with_width(2.0),
-        );
-
-        // Draw x-axis tick marks every 50 units
-        let tick_stroke = Stroke::default().with_color(Color::BLACK).with_width(1.0);
-        for x in (min_x..=max_x).step_by(50) {
-            let min_tick = Path::line(
-                (x as f32, -min_y as f32).into(),
-                (x as f32, -(min_y as f32 + 2.5)).into(),
-            );
-            let max_tick = Path::line(
-                (x as f32, -max_y as f32).into(),
-                (x as f32, -(max_y as f32 - 2.5)).into(),
-            );
-            frame.stroke(&min_tick, tick_stroke);
-            frame.stroke(&max_tick, tick_stroke);
-            frame.fill_text(Text {
-                content: x.to_string(),
-                position: (x as f32, -(min_y as f32 - 2.5)).into(),
-                color: Color::BLACK,
-                size: 4.0.into(),
-                horizontal_alignment: iced::alignment::Horizontal::Center,
-                ..Text::default()
-            });
-        }
+            Stroke::default()
+                .with_color(Color::BLACK)
+                .with_width(2.0 * line_scale),
+        );
 
-        // Draw y-axis tick marks every 50 units and flip y-coords throughout
-        for y in (min_y..=max_y).step_by(50) {
-            let min_tick = Path::line(
-                (min_x as f32, -y as f32).into(),
-                (min_x as f32 + 2.5, -y as f32).into(),
-            );
-            let max_tick = Path::line(
-                (max_x as f32, -y as f32).into(),
-                (max_x as f32 - 2.5, -y as f32).into(),
-            );
-            frame.stroke(&min_tick, tick_stroke);
-            frame.stroke(&max_tick, tick_stroke);
-            frame.fill_text(Text {
-                content: y.to_string(),
-                position: (min_x as f32 - 2.5, -y as f32 - 2.5).into(),
-                color: Color::BLACK,
-                size: 4.0.into(),
-                horizontal_alignment: iced::alignment::Horizontal::Right,
-                ..Text::default()
-            });
+        // Tick marks and labels are optional, but the boundary always renders
+        if show_axes {
+            // Draw x-axis tick marks every 50 units
+            let tick_stroke = Stroke::default()
+                .with_color(Color::BLACK)
+                .with_width(line_scale);
+            for x in ticks(min_x, max_x) {
+                let min_tick = Path::line((x, flip(min_y)).into(), (x, flip(min_y + 2.5)).into());
+                let max_tick = Path::line((x, flip(max_y)).into(), (x, flip(max_y - 2.5)).into());
+                frame.stroke(&min_tick, tick_stroke);
+                frame.stroke(&max_tick, tick_stroke);
+                frame.fill_text(Text {
+                    content: x.to_string(),
+                    position: (x, flip(min_y - 2.5)).into(),
+                    color: Color::BLACK,
+                    size: 4.0.into(),
+                    horizontal_alignment: iced::alignment::Horizontal::Center,
+                    ..Text::default()
+                });
+            }
+
+            // Draw y-axis tick marks every 50 units and flip y-coords throughout
+            for y in ticks(min_y, max_y) {
+                let min_tick = Path::line((min_x, flip(y)).into(), (min_x + 2.5, flip(y)).into());
+                let max_tick = Path::line((max_x, flip(y)).into(), (max_x - 2.5, flip(y)).into());
+                frame.stroke(&min_tick, tick_stroke);
+                frame.stroke(&max_tick, tick_stroke);
+                frame.fill_text(Text {
+                    content: y.to_string(),
+                    position: (min_x - 2.5, flip(y) - 2.5).into(),
+                    color: Color::BLACK,
+                    size: 4.0.into(),
+                    horizontal_alignment: iced::alignment::Horizontal::Right,
+                    ..Text::default()
+                });
+            }
         }
 
+        // Keep the index of hidden polygons so colors don't shift as they're revealed
         for (i, polygon) in self.polygons().enumerate() {
-            polygon.draw(i, frame);
+            if reveal(polygon) {
+                let enabled = self.is_polygon_enabled(i);
+                polygon.draw(i, frame, line_scale, fill_rule, y_down, enabled);
+            }
         }
     }
 
     /// Finds the board's bounding box by getting the min/max x and y coords
-    pub fn bounds(&self) -> (i32, i32, i32, i32) {
-        let mut min_x = i32::MAX;
-        let mut max_x = i32::MIN;
-        let mut min_y = i32::MAX;
-        let mut max_y = i32::MIN;
+    pub fn bounds(&self) -> (T, T, T, T) {
+        let mut min_x = f64::MAX;
+        let mut max_x = f64::MIN;
+        let mut min_y = f64::MAX;
+        let mut max_y = f64::MIN;
 
         for polygon in &self.polygons {
-            for vertex in polygon.vertices() {
-                min_x = min_x.min(vertex.x);
-                max_x = max_x.max(vertex.x);
-                min_y = min_y.min(vertex.y);
-                max_y = max_y.max(vertex.y);
-            }
+            let (min, max) = polygon.bounding_box();
+            min_x = min_x.min(min.x.as_());
+            max_x = max_x.max(max.x.as_());
+            min_y = min_y.min(min.y.as_());
+            max_y = max_y.max(max.y.as_());
         }
 
         // Round down/up to the nearest 100 to make it look nicer
-        min_x = (min_x / 100) * 100;
-        min_y = (min_y / 100) * 100;
-        max_x = ((max_x + 99) / 100) * 100;
-        max_y = ((max_y + 99) / 100) * 100;
+        let round_down = |v: f64| T::from_f64((v / 100.0).floor() * 100.0);
+        let round_up = |v: f64| T::from_f64((v / 100.0).ceil() * 100.0);
+
+        (
+            round_down(min_x),
+            round_down(min_y),
+            round_up(max_x),
+            round_up(max_y),
+        )
+    }
+
+    /// Returns the exact min/max x and y coordinates of the board's vertices,
+    /// e.g. for spatial indexing, unlike [`Board::bounds`] which rounds them
+    /// outward for display. Returns `None` for a board without polygons.
+    pub fn bounds_tight(&self) -> Option<(T, T, T, T)> {
+        let pick = |a: T, b: T, ordering: std::cmp::Ordering| {
+            if b.partial_cmp(&a) == Some(ordering) {
+                b
+            } else {
+                a
+            }
+        };
 
-        (min_x, min_y, max_x, max_y)
+        self.polygons
+            .iter()
+            .map(|polygon| polygon.bounding_box())
+            .map(|(min, max)| (min.x, min.y, max.x, max.y))
+            .reduce(|a, b| {
+                (
+                    pick(a.0, b.0, std::cmp::Ordering::Less),
+                    pick(a.1, b.1, std::cmp::Ordering::Less),
+                    pick(a.2, b.2, std::cmp::Ordering::Greater),
+                    pick(a.3, b.3, std::cmp::Ordering::Greater),
+                )
+            })
+    }
+
+    /// Returns the scale and translation that fit [`Board::bounds`] centered
+    /// in a canvas of the given size, at 80% of the largest size that fits.
+    /// Drawn points are then at `translation + scale * point.to_canvas_with(y_down)`.
+    pub fn canvas_transform(&self, width: f32, height: f32, y_down: bool) -> (f32, iced::Vector) {
+        let (min_x, min_y, max_x, max_y) = self.bounds();
+        let (min_x, min_y, max_x, max_y): (f32, f32, f32, f32) =
+            (min_x.as_(), min_y.as_(), max_x.as_(), max_y.as_());
+
+        let board_width = max_x - min_x;
+        let board_height = max_y - min_y;
+
+        // Calculate the scaling to center board within frame and its new size
+        let scaling: f32 = 0.8 * (width / board_width).min(height / board_height);
+        let scaled_width = board_width * scaling;
+        let scaled_height = board_height * scaling;
+
+        // Calculate translation to center the scaled board within the frame,
+        // with whichever edge is drawn at the top flush with the margin
+        let top = if y_down { -min_y } else { max_y };
+        let translation = iced::Vector::new(
+            (width - scaled_width) / 2.0 - (min_x * scaling),
+            (height - scaled_height) / 2.0 + (top * scaling),
+        );
+
+        (scaling, translation)
+    }
+
+    /// Returns a start and goal near the bottom-left and top-right corners of
+    /// [`Board::bounds`], each nudged diagonally inward until it no longer
+    /// lies inside an obstacle. If obstacles cover the whole way across, the
+    /// corner itself is used, which no obstacle can contain since none extends
+    /// past the bounds.
+    pub fn default_endpoints(&self) -> (Point<T>, Point<T>) {
+        let (min_x, min_y, max_x, max_y) = self.bounds();
+        let step = T::from_f64(5.0);
+
+        let nudge = |corner: Point<T>, dx: T, dy: T| {
+            let mut point = Point::new(corner.x + dx, corner.y + dy);
+            while self.contains_point(&point) {
+                point = Point::new(point.x + dx, point.y + dy);
+                if point.x <= min_x || point.x >= max_x || point.y <= min_y || point.y >= max_y {
+                    return corner;
+                }
+            }
+            point
+        };
+
+        (
+            nudge(Point::new(min_x, min_y), step, step),
+            nudge(Point::new(max_x, max_y), -step, -step),
+        )
     }
 
     /// Returns the total number of vertices across all polygons
@@ -152,6 +779,172 @@ impl Board {
             .map(|p| p.vertices_vec().len())
             .collect()
     }
+
+    /// Bundles the board's summary metrics into [`BoardStats`]
+    pub fn stats(&self) -> BoardStats {
+        let polygon_count = self.polygons.len();
+        let vertex_count = self.vertex_count();
+        let obstacle_area: f64 = self.polygons.iter().map(|p| p.area()).sum();
+
+        // An empty board has no bounds to measure, only sentinel extremes
+        let board_area = if polygon_count > 0 {
+            let (min_x, min_y, max_x, max_y) = self.bounds();
+            let width: f64 = (max_x - min_x).as_();
+            let height: f64 = (max_y - min_y).as_();
+            width * height
+        } else {
+            0.0
+        };
+
+        BoardStats {
+            polygon_count,
+            vertex_count,
+            avg_vertices_per_polygon: if polygon_count > 0 {
+                vertex_count as f64 / polygon_count as f64
+            } else {
+                0.0
+            },
+            obstacle_area,
+            coverage: if board_area > 0.0 {
+                obstacle_area / board_area
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Returns the minimum and maximum corners of a [`Polygon`] that is exactly an
+/// axis-aligned rectangle
+fn as_rectangle<T: Coordinate>(polygon: &Polygon<T>) -> Option<(Point<T>, Point<T>)> {
+    let (min, max) = polygon.bounding_box();
+    let at_corners = polygon
+        .vertices()
+        .all(|v| (v.x == min.x || v.x == max.x) && (v.y == min.y || v.y == max.y));
+    let box_area: f64 = ((max.x - min.x) * (max.y - min.y)).as_();
+
+    (polygon.vertices().count() == 4 && at_corners && polygon.area() == box_area && box_area > 0.0)
+        .then_some((min, max))
+}
+
+/// Repeatedly replaces pairs of `adjacent` rectangles with their union until
+/// no pair is left
+fn merge_rectangles<T: Coordinate>(
+    rects: &mut Vec<(Point<T>, Point<T>)>,
+    adjacent: impl Fn(&(Point<T>, Point<T>), &(Point<T>, Point<T>)) -> bool,
+) {
+    let min = |a: T, b: T| if b < a { b } else { a };
+    let max = |a: T, b: T| if b > a { b } else { a };
+
+    'merging: loop {
+        for i in 0..rects.len() {
+            for j in i + 1..rects.len() {
+                if adjacent(&rects[i], &rects[j]) {
+                    let (a, b) = (rects[i], rects.remove(j));
+                    rects[i] = (
+                        Point::new(min(a.0.x, b.0.x), min(a.0.y, b.0.y)),
+                        Point::new(max(a.1.x, b.1.x), max(a.1.y, b.1.y)),
+                    );
+                    continue 'merging;
+                }
+            }
+        }
+        break;
+    }
+}
+
+/// Tick positions every 50 units from `min` up to and including `max`
+pub(crate) fn ticks(min: f32, max: f32) -> impl Iterator<Item = f32> {
+    (0..)
+        .map(move |i| min + 50.0 * i as f32)
+        .take_while(move |&v| v <= max)
+}
+
+impl Board {
+    /// Generates a reproducible board of random convex obstacles covering the
+    /// same area as [`sample_board`].
+    ///
+    /// The area is split into a grid of cells, each of which may hold the
+    /// convex hull of a few random points inset from its borders, so that
+    /// obstacles never overlap and always leave corridors between them.
+    pub fn random(seed: u64) -> Self {
+        const CELL: i32 = 50;
+        const INSET: i32 = 5;
+        const POINTS_PER_POLYGON: usize = 5;
+
+        let mut rng = SplitMix64::new(seed);
+        let mut polygons = Vec::new();
+
+        for x in (100..400).step_by(CELL as usize) {
+            for y in (500..700).step_by(CELL as usize) {
+                // Leave roughly a third of the cells empty
+                if rng.next_u64().is_multiple_of(3) {
+                    continue;
+                }
+
+                let points: Vec<Point> = (0..POINTS_PER_POLYGON)
+                    .map(|_| {
+                        Point::new(
+                            rng.next_range(x + INSET, x + CELL - INSET),
+                            rng.next_range(y + INSET, y + CELL - INSET),
+                        )
+                    })
+                    .collect();
+
+                let hull = Polygon::convex_hull(&points);
+                if hull.vertices().count() >= 3 {
+                    polygons.push(hull);
+                }
+            }
+        }
+
+        Self::new(polygons)
+    }
+
+    /// Builds a board from an ASCII map where `#` marks a wall and any other
+    /// character is open space, emitting one `cell_size` square per wall.
+    ///
+    /// The first row is the top of the board, so the map reads the same way
+    /// it renders. Blank lines before and after the map are ignored, as is
+    /// indentation common to every row, so a map can be indented in source;
+    /// any other leading character, even a space, is an open cell.
+    pub fn from_grid(grid: &str, cell_size: i32) -> Self {
+        let is_blank = |row: &&str| row.trim().is_empty();
+        let lines: Vec<&str> = grid.lines().collect();
+        let first = lines.iter().position(|row| !is_blank(row));
+        let last = lines.iter().rposition(|row| !is_blank(row));
+        let lines = match (first, last) {
+            (Some(first), Some(last)) => &lines[first..=last],
+            _ => &[][..],
+        };
+
+        let indent = lines
+            .iter()
+            .filter(|row| !is_blank(row))
+            .map(|row| row.len() - row.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let rows: Vec<&str> = lines
+            .iter()
+            .map(|row| row.get(indent..).unwrap_or(""))
+            .collect();
+
+        let mut polygons = Vec::new();
+        for (r, row) in rows.iter().enumerate() {
+            let y = (rows.len() - 1 - r) as i32 * cell_size;
+            for (c, _) in row.chars().enumerate().filter(|&(_, ch)| ch == '#') {
+                let x = c as i32 * cell_size;
+                polygons.push(Polygon::new(vec![
+                    Point::new(x, y),
+                    Point::new(x, y + cell_size),
+                    Point::new(x + cell_size, y + cell_size),
+                    Point::new(x + cell_size, y),
+                ]));
+            }
+        }
+
+        Self::new(polygons)
+    }
 }
 
 /// Create a sample board with some polygons
@@ -210,3 +1003,617 @@ pub fn sample_board() -> Board {
 
     Board::new(polygons)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::VisibilityGraphPathfinder;
+    use crate::{Heuristic, Pathfinder};
+
+    fn create_test_board() -> Board {
+        Board::new(vec![Polygon::new(vec![
+            (40, 40).into(),
+            (40, 60).into(),
+            (60, 60).into(),
+            (60, 40).into(),
+        ])])
+    }
+
+    #[test]
+    fn test_optimal_path_is_valid() {
+        let board = create_test_board();
+        let search = VisibilityGraphPathfinder::new(
+            board.clone(),
+            Point::new(0, 0),
+            Point::new(100, 100),
+            Heuristic::Euclidean,
+        );
+
+        let (path, _) = search.get_optimal_path().unwrap();
+        assert!(board.path_is_valid(path), "Optimal path should be valid");
+    }
+
+    #[test]
+    fn test_crossing_path_is_invalid() {
+        let board = create_test_board();
+
+        let through = [Point::new(0, 50), Point::new(100, 50)];
+        assert!(
+            !board.path_is_valid(&through),
+            "Path straight through an obstacle should be invalid"
+        );
+
+        let inside = [Point::new(0, 0), Point::new(50, 50)];
+        assert!(
+            !board.path_is_valid(&inside),
+            "Path ending inside an obstacle should be invalid"
+        );
+    }
+
+    #[test]
+    fn test_scaled_doubles_vertices() {
+        let board = create_test_board();
+        let scaled = board.scaled(2.0, Point::ORIGIN);
+
+        for (original, scaled) in board.polygons().zip(scaled.polygons()) {
+            for (v, s) in original.vertices().zip(scaled.vertices()) {
+                assert_eq!(*s, Point::new(v.x * 2, v.y * 2));
+            }
+        }
+
+        assert_eq!(
+            Point::new(10, 15).scaled(2.0, Point::ORIGIN),
+            Point::new(20, 30),
+            "Endpoints should scale alongside the board"
+        );
+    }
+
+    #[test]
+    fn test_sample_board_stats() {
+        let board = sample_board();
+        let stats = board.stats();
+
+        assert_eq!(stats.polygon_count, 8);
+        assert_eq!(stats.vertex_count, board.vertex_count());
+        assert!(stats.obstacle_area > 0.0);
+        assert!((0.0..=1.0).contains(&stats.coverage));
+    }
+
+    #[test]
+    fn test_empty_board_stats() {
+        let mut board = create_test_board();
+        board.remove_polygon(0);
+        let stats = board.stats();
+
+        assert_eq!(stats.polygon_count, 0);
+        assert_eq!(stats.vertex_count, 0);
+        assert_eq!(stats.avg_vertices_per_polygon, 0.0);
+        assert_eq!(stats.obstacle_area, 0.0);
+        assert_eq!(stats.coverage, 0.0);
+    }
+
+    #[test]
+    fn test_default_endpoints_are_free() {
+        let board = sample_board();
+        let (start, goal) = board.default_endpoints();
+
+        assert!(
+            !board.contains_point(&start),
+            "Start should be outside obstacles"
+        );
+        assert!(
+            !board.contains_point(&goal),
+            "Goal should be outside obstacles"
+        );
+        assert!(start.x < goal.x && start.y < goal.y);
+
+        // An obstacle filling the bounds leaves only its own corners free
+        let covered = Board::new(vec![Polygon::new(vec![
+            (0, 0).into(),
+            (100, 0).into(),
+            (100, 100).into(),
+            (0, 100).into(),
+        ])]);
+        let (start, goal) = covered.default_endpoints();
+        assert_eq!((start, goal), (Point::new(0, 0), Point::new(100, 100)));
+        assert!(!covered.contains_point(&start) && !covered.contains_point(&goal));
+    }
+
+    #[test]
+    fn test_polygon_is_near_explored_points() {
+        let board = create_test_board();
+        let polygon = board.polygons().next().unwrap();
+
+        assert!(polygon.is_near(&[Point::new(40, 40)], 0.0));
+        assert!(polygon.is_near(&[Point::new(0, 0), Point::new(30, 40)], 10.0));
+        assert!(!polygon.is_near(&[Point::new(0, 0)], 10.0));
+        assert!(!polygon.is_near(&[], 100.0));
+    }
+
+    #[test]
+    fn test_bounds_is_union_of_bounding_boxes() {
+        let board = sample_board();
+        let (min, max) = board
+            .polygons()
+            .map(|p| p.bounding_box())
+            .reduce(|(lo, hi), (min, max)| {
+                (
+                    Point::new(lo.x.min(min.x), lo.y.min(min.y)),
+                    Point::new(hi.x.max(max.x), hi.y.max(max.y)),
+                )
+            })
+            .unwrap();
+
+        let round_down = |v: i32| v.div_euclid(100) * 100;
+        let round_up = |v: i32| -(-v).div_euclid(100) * 100;
+        assert_eq!(
+            board.bounds(),
+            (
+                round_down(min.x),
+                round_down(min.y),
+                round_up(max.x),
+                round_up(max.y)
+            )
+        );
+    }
+
+    #[test]
+    fn test_bounds_tight() {
+        let square = |(x0, y0): (i32, i32), (x1, y1): (i32, i32)| {
+            Polygon::new(vec![
+                (x0, y0).into(),
+                (x0, y1).into(),
+                (x1, y1).into(),
+                (x1, y0).into(),
+            ])
+        };
+
+        let board = Board::new(vec![square((0, 0), (100, 100))]);
+        assert_eq!(board.bounds_tight(), Some((0, 0, 100, 100)));
+        assert_eq!(board.bounds(), (0, 0, 100, 100));
+
+        let board = Board::new(vec![
+            square((0, 0), (100, 100)),
+            square((-30, 40), (120, 155)),
+        ]);
+        assert_eq!(board.bounds_tight(), Some((-30, 0, 120, 155)));
+        assert_eq!(board.bounds(), (-100, 0, 200, 200));
+
+        assert_eq!(Board::<i32>::new(Vec::new()).bounds_tight(), None);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let board = Board::from_grid(
+            "
+            ...
+            .#.
+            ...
+            ",
+            10,
+        );
+
+        assert_eq!(
+            board.polygons().collect::<Vec<_>>(),
+            vec![&Polygon::new(vec![
+                (10, 10).into(),
+                (10, 20).into(),
+                (20, 20).into(),
+                (20, 10).into(),
+            ])]
+        );
+
+        // Open cells at the start of a row keep the walls after them in place
+        let board = Board::from_grid(
+            "
+            #..
+              #
+            ",
+            10,
+        );
+        let corners: Vec<_> = board
+            .polygons()
+            .map(|polygon| polygon.bounding_box().0)
+            .collect();
+        assert_eq!(corners, [Point::new(0, 10), Point::new(20, 0)]);
+    }
+
+    #[test]
+    fn test_merge_axis_aligned_rectangles() {
+        let mut board = Board::from_grid("#####", 10);
+        assert_eq!(board.polygons().count(), 5);
+
+        board.merge_axis_aligned_rectangles();
+        let polygons: Vec<_> = board.polygons().collect();
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].vertices().count(), 4);
+        assert_eq!(polygons[0].bounding_box(), ((0, 0).into(), (50, 10).into()));
+    }
+
+    #[test]
+    fn test_random_board_is_reproducible() {
+        let board = Board::random(7);
+
+        assert!(board.polygons().count() > 0);
+        assert!(board.polygons().all(|p| p.vertices().count() >= 3));
+        assert_eq!(
+            board.polygons().collect::<Vec<_>>(),
+            Board::random(7).polygons().collect::<Vec<_>>(),
+            "Same seed should give the same board"
+        );
+        assert_ne!(
+            board.polygons().collect::<Vec<_>>(),
+            Board::random(8).polygons().collect::<Vec<_>>(),
+            "Different seeds should give different boards"
+        );
+    }
+
+    #[test]
+    fn test_remove_polygons_in_rect() {
+        let square = |x: i32, y: i32| {
+            Polygon::new(vec![
+                (x, y).into(),
+                (x, y + 10).into(),
+                (x + 10, y + 10).into(),
+                (x + 10, y).into(),
+            ])
+        };
+        let mut board = Board::new(vec![square(0, 0), square(20, 0), square(40, 0)]);
+
+        // Encloses the first two squares and only part of the third
+        board.remove_polygons_in_rect(Point::new(-5, -5), Point::new(45, 15));
+
+        assert_eq!(board.polygons().collect::<Vec<_>>(), vec![&square(40, 0)]);
+    }
+
+    #[test]
+    fn test_closest_obstacle() {
+        let mut board = create_test_board();
+        board.polygons.push(Polygon::new(vec![
+            (0, 0).into(),
+            (0, 10).into(),
+            (10, 10).into(),
+        ]));
+
+        // Just right of the square's right edge
+        let (index, distance) = board.closest_obstacle(&Point::new(62, 50)).unwrap();
+        assert_eq!(index, 0);
+        assert!((distance - 2.0).abs() < 1e-9, "Distance was {distance}");
+
+        assert_eq!(board.closest_obstacle(&Point::new(50, 50)), Some((0, 0.0)));
+        assert_eq!(Board::new(vec![]).closest_obstacle(&Point::ORIGIN), None);
+    }
+
+    #[test]
+    fn test_narrow_segments() {
+        let board = create_test_board();
+        // The first segment grazes the square's (60, 40) corner, 2 units off
+        let path = [Point::new(0, 38), Point::new(100, 38), Point::new(100, 100)];
+
+        assert!((board.segment_clearance(&path[0], &path[1]) - 2.0).abs() < 1e-9);
+        assert_eq!(board.narrow_segments(&path, 5.0), vec![0]);
+        assert!(board.narrow_segments(&path, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let board = create_test_board();
+        let extra = Polygon::new(vec![(0, 0).into(), (0, 10).into(), (10, 10).into()]);
+        let mut edited = board.clone();
+        edited.add_polygon(extra.clone());
+
+        let diff = board.diff(&edited);
+        assert_eq!(diff.added, vec![extra.clone()]);
+        assert!(diff.removed.is_empty());
+
+        assert_eq!(edited.diff(&board).removed, vec![extra]);
+        assert_eq!(
+            board.diff(&board),
+            BoardDiff {
+                added: vec![],
+                removed: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn test_try_add_polygon() {
+        let mut board = create_test_board();
+        let square = |x: i32, y: i32| {
+            Polygon::new(vec![
+                (x, y).into(),
+                (x, y + 10).into(),
+                (x + 10, y + 10).into(),
+                (x + 10, y).into(),
+            ])
+        };
+
+        assert_eq!(board.try_add_polygon(square(0, 0)), Ok(()));
+        assert_eq!(board.polygons().count(), 2);
+
+        let segment = Polygon::new(vec![(70, 70).into(), (80, 80).into()]);
+        assert_eq!(
+            board.try_add_polygon(segment),
+            Err(BoardError::TooFewVertices(2))
+        );
+
+        let bowtie = Polygon::new(vec![
+            (70, 70).into(),
+            (80, 80).into(),
+            (80, 70).into(),
+            (70, 80).into(),
+        ]);
+        assert_eq!(
+            board.try_add_polygon(bowtie),
+            Err(BoardError::SelfIntersecting)
+        );
+
+        assert_eq!(
+            board.try_add_polygon(square(55, 55)),
+            Err(BoardError::Overlapping(0))
+        );
+        assert_eq!(
+            board.try_add_polygon(square(45, 45)),
+            Err(BoardError::Overlapping(0)),
+            "Fully inside"
+        );
+        let existing = board.polygons().next().unwrap().clone();
+        assert_eq!(
+            board.try_add_polygon(existing),
+            Err(BoardError::Overlapping(0)),
+            "Identical"
+        );
+        assert_eq!(board.polygons().count(), 2);
+    }
+
+    #[test]
+    fn test_reflect_and_rotate() {
+        let board = sample_board();
+        let polygons = |board: &Board| board.polygons().cloned().collect::<Vec<_>>();
+
+        assert_eq!(
+            polygons(&board.reflected_x().reflected_x()),
+            polygons(&board)
+        );
+        assert_eq!(
+            polygons(&board.reflected_y().reflected_y()),
+            polygons(&board)
+        );
+        assert_ne!(polygons(&board.rotated_90()), polygons(&board));
+        assert_eq!(
+            polygons(&board.rotated_90().rotated_90().rotated_90().rotated_90()),
+            polygons(&board)
+        );
+        assert_eq!(
+            polygons(&board.rotated_90().rotated_90()),
+            polygons(&board.reflected_x().reflected_y())
+        );
+
+        let (min_x, min_y, max_x, max_y) = create_test_board().rotated_90().bounds();
+        assert_eq!((min_x, min_y, max_x, max_y), (-100, 0, 0, 100));
+    }
+
+    #[test]
+    fn test_canvas_transform() {
+        // Bounds round out to (0, 0) to (100, 100), fit into 500 x 500 at 4x
+        let board = create_test_board();
+        let vertex = Point::new(40, 60);
+        let screen = |y_down| {
+            let (scale, translation) = board.canvas_transform(500.0, 500.0, y_down);
+            let canvas = vertex.to_canvas_with(y_down);
+            iced::Point::new(canvas.x * scale, canvas.y * scale) + translation
+        };
+
+        assert_eq!(screen(false), iced::Point::new(210.0, 210.0));
+        assert_eq!(screen(true), iced::Point::new(210.0, 290.0));
+    }
+
+    #[test]
+    fn test_vertices_ordered() {
+        // Two squares sharing the edge from (60, 40) to (60, 60)
+        let mut board = create_test_board();
+        board.add_polygon(Polygon::new(vec![
+            (60, 40).into(),
+            (60, 60).into(),
+            (80, 60).into(),
+            (80, 40).into(),
+        ]));
+
+        let vertices = board.vertices_ordered();
+        assert_eq!(vertices, board.vertices_ordered());
+        assert_eq!(vertices.len(), board.vertices().len());
+        assert_eq!(vertices.len(), 6);
+        assert_eq!(vertices[0], Point::new(40, 40));
+        assert!(vertices
+            .windows(2)
+            .all(|w| (w[0].x, w[0].y) < (w[1].x, w[1].y)));
+    }
+
+    #[test]
+    fn test_free_space_components() {
+        let wall = |(x0, y0): (i32, i32), (x1, y1): (i32, i32)| {
+            Polygon::new(vec![
+                (x0, y0).into(),
+                (x0, y1).into(),
+                (x1, y1).into(),
+                (x1, y0).into(),
+            ])
+        };
+        // A ring of four overlapping walls sealing off the middle
+        let ring = Board::new(vec![
+            wall((0, 0), (10, 100)),
+            wall((90, 0), (100, 100)),
+            wall((0, 0), (100, 10)),
+            wall((0, 90), (100, 100)),
+        ]);
+        let (outside, inside) = (Point::new(-50, 50), Point::new(50, 50));
+
+        let labels = ring.free_space_components(&[outside, inside]);
+        assert!(labels.iter().all(Option::is_some));
+        assert_ne!(labels[0], labels[1]);
+        assert!(!ring.same_free_region(&outside, &inside));
+        assert!(ring.same_free_region(&outside, &Point::new(150, 150)));
+        assert!(ring.same_free_region(&inside, &Point::new(20, 80)));
+        assert_eq!(ring.free_space_components(&[Point::new(5, 50)]), [None]);
+
+        // Two sealed pockets that see no vertex still get labels of their own
+        let rings = Board::new(vec![
+            wall((0, 0), (10, 100)),
+            wall((90, 0), (100, 100)),
+            wall((0, 0), (100, 10)),
+            wall((0, 90), (100, 100)),
+            wall((200, 0), (210, 100)),
+            wall((290, 0), (300, 100)),
+            wall((200, 0), (300, 10)),
+            wall((200, 90), (300, 100)),
+        ]);
+        let labels = rings.free_space_components(&[inside, Point::new(250, 50), outside]);
+        assert_eq!(labels.len(), 3);
+        assert!(labels.iter().all(Option::is_some));
+        assert_ne!(labels[0], labels[1]);
+        assert_ne!(labels[0], labels[2]);
+        assert_ne!(labels[1], labels[2]);
+        assert!(!rings.is_reachable(&inside, &Point::new(250, 50)));
+
+        let empty = Board::<i32>::new(Vec::new());
+        assert!(empty.same_free_region(&outside, &inside));
+    }
+
+    #[test]
+    fn test_is_reachable() {
+        let board = create_test_board();
+        assert!(board.is_reachable(&Point::new(0, 0), &Point::new(100, 100)));
+        assert!(board.is_reachable(&Point::new(0, 50), &Point::new(100, 50)));
+        assert!(!board.is_reachable(&Point::new(0, 0), &Point::new(50, 50)));
+
+        // Four overlapping walls sealing off the middle
+        let wall = |(x0, y0): (i32, i32), (x1, y1): (i32, i32)| {
+            Polygon::new(vec![
+                (x0, y0).into(),
+                (x0, y1).into(),
+                (x1, y1).into(),
+                (x1, y0).into(),
+            ])
+        };
+        let boxed = Board::new(vec![
+            wall((0, 0), (10, 100)),
+            wall((90, 0), (100, 100)),
+            wall((0, 0), (100, 10)),
+            wall((0, 90), (100, 100)),
+        ]);
+        assert!(boxed.is_reachable(&Point::new(30, 30), &Point::new(70, 60)));
+        assert!(!boxed.is_reachable(&Point::new(50, 50), &Point::new(150, 50)));
+        assert!(boxed.is_reachable(&Point::new(150, 50), &Point::new(-50, 50)));
+    }
+
+    #[test]
+    fn test_canonical_form() {
+        let triangle = Polygon::new(vec![(0, 0).into(), (10, 0).into(), (5, 10).into()]);
+        let square = Polygon::new(vec![
+            (20, 20).into(),
+            (30, 20).into(),
+            (30, 30).into(),
+            (20, 30).into(),
+        ]);
+        let board = Board::new(vec![triangle, square]);
+
+        // Same obstacles, listed in another order, rotated and reversed
+        let shuffled = Board::new(vec![
+            Polygon::new(vec![
+                (30, 30).into(),
+                (20, 30).into(),
+                (20, 20).into(),
+                (30, 20).into(),
+            ]),
+            Polygon::new(vec![(5, 10).into(), (10, 0).into(), (0, 0).into()]),
+        ]);
+
+        let (a, b) = (board.canonical_form(), shuffled.canonical_form());
+        assert_eq!(
+            a.polygons().collect::<Vec<_>>(),
+            b.polygons().collect::<Vec<_>>()
+        );
+        assert_ne!(
+            board.polygons().collect::<Vec<_>>(),
+            shuffled.polygons().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            a.polygons().next().unwrap().vertices().next(),
+            Some(&Point::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn test_gap_clearance() {
+        let mut board = create_test_board();
+        let (from, to) = (Point::new(50, 0), Point::new(50, 100));
+        assert_eq!(
+            board.gap_clearance(&from, &to),
+            f64::INFINITY,
+            "One obstacle"
+        );
+
+        // A second square leaving a 10 unit gap to the right of the first,
+        // with 5 units of room on either side of its middle
+        board.add_polygon(Polygon::new(vec![
+            (70, 40).into(),
+            (70, 60).into(),
+            (90, 60).into(),
+            (90, 40).into(),
+        ]));
+        let (from, to) = (Point::new(65, 0), Point::new(65, 100));
+        assert_eq!(board.gap_clearance(&from, &to), 5.0);
+        let below = board.gap_clearance(&Point::new(0, 0), &Point::new(100, 0));
+        assert!(below > 40.0, "{below}");
+    }
+
+    #[test]
+    fn test_remove_polygon_keeps_disabled() {
+        let mut board = sample_board();
+        board.set_polygon_enabled(1, false);
+        board.set_polygon_enabled(3, false);
+
+        board.remove_polygon(0);
+        assert!(board.is_polygon_enabled(1));
+        assert!(!board.is_polygon_enabled(0));
+        assert!(!board.is_polygon_enabled(2));
+
+        board.remove_polygon(0);
+        assert_eq!(
+            board.enabled_polygons().count(),
+            board.polygons().count() - 1
+        );
+    }
+
+    #[test]
+    fn test_polygon_at() {
+        let mut board = create_test_board();
+
+        assert_eq!(board.polygon_at(&Point::new(50, 50)), Some(0));
+        assert_eq!(board.polygon_at(&Point::new(40, 50)), None, "Boundary");
+        assert_eq!(board.polygon_at(&Point::new(0, 0)), None);
+
+        board.remove_polygon(0);
+        assert_eq!(board.polygon_at(&Point::new(50, 50)), None);
+    }
+
+    #[test]
+    fn test_blocking_polygon() {
+        let board = create_test_board();
+
+        let (from, to) = (Point::new(0, 50), Point::new(100, 50));
+        assert_eq!(board.blocking_polygon(&from, &to), Some(0));
+        assert!(!board.line_of_sight(&from, &to));
+
+        let (from, to) = (Point::new(0, 0), Point::new(100, 0));
+        assert_eq!(board.blocking_polygon(&from, &to), None);
+    }
+
+    #[test]
+    fn test_edges_iter_matches_outer_edges() {
+        let board = sample_board();
+        let edges: Vec<_> = board.edges_iter().collect();
+
+        assert_eq!(edges, board.outer_edges());
+    }
+}
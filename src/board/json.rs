@@ -0,0 +1,176 @@
+//! Versioned JSON format for saving and loading boards.
+//!
+//! Documents look like `{"version": 1, "polygons": [[[x, y], ...], ...]}`,
+//! with each polygon given as its list of vertices. Boards with disabled
+//! polygons also list their indices, as in `"disabled": [0, 2]`, which older
+//! documents simply leave out.
+use serde::{Deserialize, Serialize};
+
+use crate::{Board, Point, Polygon};
+
+/// The format version written by [`Board::to_json`]
+pub const CURRENT_VERSION: u32 = 1;
+
+/// Errors raised while loading or editing a [`Board`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardError {
+    /// The document declares a format version this build can't read
+    UnsupportedVersion(u32),
+    /// The document isn't valid JSON or doesn't match the format
+    Invalid(String),
+    /// A polygon has fewer than three vertices
+    TooFewVertices(usize),
+    /// A polygon's outline crosses itself
+    SelfIntersecting,
+    /// A polygon overlaps the existing polygon at this index
+    Overlapping(usize),
+}
+
+impl std::fmt::Display for BoardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported board format version {version} (expected {CURRENT_VERSION})"
+            ),
+            BoardError::Invalid(reason) => write!(f, "invalid board file: {reason}"),
+            BoardError::TooFewVertices(count) => {
+                write!(f, "polygon has {count} vertices (expected at least 3)")
+            }
+            BoardError::SelfIntersecting => write!(f, "polygon outline crosses itself"),
+            BoardError::Overlapping(index) => write!(f, "polygon overlaps polygon {index}"),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+impl From<serde_json::Error> for BoardError {
+    fn from(error: serde_json::Error) -> Self {
+        BoardError::Invalid(error.to_string())
+    }
+}
+
+/// Just enough of any document to tell which version it is
+#[derive(Deserialize)]
+struct Versioned {
+    version: u32,
+}
+
+/// Reads the version of any document without the rest of it, so it can be
+/// checked before committing to a layout
+pub(crate) fn document_version(json: &str) -> Result<u32, BoardError> {
+    let Versioned { version } = serde_json::from_str(json)?;
+    Ok(version)
+}
+
+/// Version 1 of the board layout, also embedded in scenario documents
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BoardV1 {
+    version: u32,
+    polygons: Vec<Vec<[i32; 2]>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    disabled: Vec<usize>,
+}
+
+impl BoardV1 {
+    pub(crate) fn new(board: &Board) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            polygons: board
+                .polygons()
+                .map(|polygon| polygon.vertices().map(|&v| v.into()).collect())
+                .collect(),
+            disabled: (0..board.polygons().count())
+                .filter(|&i| !board.is_polygon_enabled(i))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn into_board(self) -> Board {
+        let mut board = Board::new(
+            self.polygons
+                .into_iter()
+                .map(|vertices| Polygon::new(vertices.into_iter().map(Point::from).collect()))
+                .collect(),
+        );
+        for index in self.disabled {
+            board.set_polygon_enabled(index, false);
+        }
+        board
+    }
+}
+
+impl Board {
+    /// Serializes the board to the current versioned JSON format
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&BoardV1::new(self)).expect("Board should always serialize")
+    }
+
+    /// Loads a board from versioned JSON, checking the version before reading
+    /// the rest of the document
+    pub fn from_json(json: &str) -> Result<Self, BoardError> {
+        match document_version(json)? {
+            1 => Ok(serde_json::from_str::<BoardV1>(json)?.into_board()),
+            version => Err(BoardError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::sample_board;
+
+    #[test]
+    fn test_load_v1() {
+        let json = r#"{"version": 1, "polygons": [[[0, 0], [10, 0], [10, 10]]]}"#;
+        let board = Board::from_json(json).unwrap();
+
+        assert_eq!(
+            board.polygons().collect::<Vec<_>>(),
+            vec![&Polygon::new(vec![
+                (0, 0).into(),
+                (10, 0).into(),
+                (10, 10).into()
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let board = sample_board();
+        let loaded = Board::from_json(&board.to_json()).unwrap();
+
+        assert_eq!(
+            loaded.polygons().collect::<Vec<_>>(),
+            board.polygons().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_round_trip_disabled() {
+        let mut board = sample_board();
+        board.set_polygon_enabled(1, false);
+        let json = board.to_json();
+        let loaded = Board::from_json(&json).unwrap();
+
+        assert!(json.contains(r#""disabled":[1]"#));
+        assert!(!loaded.is_polygon_enabled(1));
+        assert_eq!(
+            loaded.enabled_polygons().collect::<Vec<_>>(),
+            board.enabled_polygons().collect::<Vec<_>>()
+        );
+        assert!(!sample_board().to_json().contains("disabled"));
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let json = r#"{"version": 999, "polygons": []}"#;
+
+        assert_eq!(
+            Board::from_json(json).unwrap_err(),
+            BoardError::UnsupportedVersion(999)
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Straight-line trajectories that reflect off obstacles, as an alternative
+//! to shortest paths.
+use crate::{Board, Edge, Point, Vector};
+
+/// Hits closer than this to the current position are ignored, so the ray
+/// doesn't immediately re-hit the edge it just bounced off
+const MIN_TRAVEL: f32 = 1e-3;
+
+/// Traces a ray from `start` along `direction`, reflecting off obstacle edges
+/// and the board's [`Board::bounds`], and returns every point where it hits
+/// something, after `start` itself.
+///
+/// The ray stops at the hit following its `max_bounces`th reflection, or early
+/// if it escapes the board. Points are rounded to integer coordinates only on
+/// output, so rounding doesn't accumulate from one bounce to the next.
+pub fn trace_bounce(
+    board: &Board,
+    start: Point,
+    direction: Vector<f32>,
+    max_bounces: usize,
+) -> Vec<Point> {
+    let to_f32 = |p: Point| Point::new(p.x as f32, p.y as f32);
+    let to_i32 = |p: Point<f32>| Point::new(p.x.round() as i32, p.y.round() as i32);
+
+    let (min_x, min_y, max_x, max_y) = board.bounds();
+    let corners = [
+        Point::new(min_x, min_y),
+        Point::new(max_x, min_y),
+        Point::new(max_x, max_y),
+        Point::new(min_x, max_y),
+    ];
+    let walls: Vec<Edge<f32>> = board
+        .edges_iter()
+        .chain((0..4).map(|i| Edge::new(corners[i], corners[(i + 1) % 4])))
+        .map(|edge| Edge::new(to_f32(edge.start), to_f32(edge.end)))
+        .collect();
+
+    // Far enough to cross the whole board from anywhere inside it
+    let reach = 2.0 * ((max_x - min_x) + (max_y - min_y)) as f32;
+
+    let mut points = vec![start];
+    let mut position = to_f32(start);
+    let mut direction = direction;
+
+    for _ in 0..=max_bounces {
+        let length = direction.x.hypot(direction.y);
+        if length == 0.0 {
+            break;
+        }
+        let ray = Edge::new(position, position + direction * (reach / length));
+
+        let nearest = walls
+            .iter()
+            .filter_map(|wall| {
+                let hit = ray.intersection_point(wall)?;
+                let travel = hit.distance(position);
+                (travel > MIN_TRAVEL).then_some((travel, hit, wall))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        let Some((_, hit, wall)) = nearest else {
+            break;
+        };
+
+        points.push(to_i32(hit));
+        position = hit;
+
+        let along = wall.end - wall.start;
+        direction = direction.reflect(Vector::new(-along.y, along.x));
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polygon;
+
+    #[test]
+    fn test_reflect() {
+        let reflected = Vector::new(1.0, 0.0).reflect(Vector::new(1.0, 1.0));
+        assert!((reflected.x - 0.0).abs() < 1e-6 && (reflected.y + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bounce_off_45_degree_wall() {
+        // The hypotenuse lies along x + y = 100, facing down and to the left
+        let board = Board::new(vec![Polygon::new(vec![
+            (40, 60).into(),
+            (60, 40).into(),
+            (60, 60).into(),
+        ])]);
+
+        let path = trace_bounce(&board, Point::new(0, 50), Vector::new(1.0, 0.0), 1);
+
+        // A horizontal ray hitting the wall should turn straight down
+        assert_eq!(
+            path,
+            vec![Point::new(0, 50), Point::new(50, 50), Point::new(50, 0)]
+        );
+    }
+}
@@ -0,0 +1,430 @@
+//! Helpers for handing paths and boards to external consumers.
+use std::collections::{HashMap, HashSet};
+
+use crate::{Board, Point, Vector};
+
+#[cfg(feature = "pdf")]
+use crate::board::ticks;
+#[cfg(feature = "pdf")]
+use num_traits::AsPrimitive;
+
+/// Snaps a floating point path onto an integer grid of the given spacing.
+///
+/// Each point is rounded to the nearest multiple of `grid`. If that would make
+/// the segment from the previous point cross an obstacle, or land inside one,
+/// the point is instead kept at its nearest integer coordinates.
+pub fn snap_path_to_grid(board: &Board, path: &[Point<f32>], grid: i32) -> Vec<Point<i32>> {
+    let grid = grid.max(1);
+    let snap = |value: f32| (value / grid as f32).round() as i32 * grid;
+
+    let mut snapped: Vec<Point> = Vec::with_capacity(path.len());
+
+    for point in path {
+        let candidate = Point::new(snap(point.x), snap(point.y));
+        let segment_is_valid = match snapped.last() {
+            Some(previous) => board.path_is_valid(&[*previous, candidate]),
+            None => board.path_is_valid(&[candidate]),
+        };
+
+        if segment_is_valid {
+            snapped.push(candidate);
+        } else {
+            snapped.push(Point::new(point.x.round() as i32, point.y.round() as i32));
+        }
+    }
+
+    snapped
+}
+
+/// Serializes a visibility graph as an adjacency list in JSON, of the form
+/// `{"nodes": [[x, y], ...], "edges": [[i, j], ...]}`.
+///
+/// Nodes are ordered by coordinate and edges refer to them by index. Each
+/// undirected edge is listed once, with the lower index first.
+pub fn visibility_graph_to_json(graph: &HashMap<Point, HashSet<Point>>) -> String {
+    let mut nodes: Vec<Point> = graph
+        .iter()
+        .flat_map(|(from, neighbors)| std::iter::once(from).chain(neighbors))
+        .copied()
+        .collect();
+    nodes.sort_by_key(|p| (p.x, p.y));
+    nodes.dedup();
+
+    let index: HashMap<Point, usize> = nodes.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+    let mut edges: Vec<[usize; 2]> = graph
+        .iter()
+        .flat_map(|(from, neighbors)| neighbors.iter().map(move |to| (from, to)))
+        .map(|(from, to)| {
+            let (i, j) = (index[from], index[to]);
+            [i.min(j), i.max(j)]
+        })
+        .filter(|[i, j]| i != j)
+        .collect();
+    edges.sort();
+    edges.dedup();
+
+    let nodes: Vec<[i32; 2]> = nodes.iter().map(|p| [p.x, p.y]).collect();
+    serde_json::json!({ "nodes": nodes, "edges": edges }).to_string()
+}
+
+/// Writes the board's obstacles as flat faces in Wavefront OBJ, e.g. to
+/// extrude them into a 3D scene.
+///
+/// The board lies in the XZ plane at y = 0, with board y mapped to -z so
+/// that each face, wound counterclockwise as in [`Polygon::canonical_form`],
+/// faces up along +y.
+///
+/// [`Polygon::canonical_form`]: crate::Polygon::canonical_form
+pub fn export_obj(board: &Board, out: &mut impl std::io::Write) -> std::io::Result<()> {
+    // OBJ indices are 1-based and count vertices across the whole file
+    let mut next_index = 1;
+
+    for (i, polygon) in board.polygons().enumerate() {
+        let polygon = polygon.canonical_form();
+        writeln!(out, "o obstacle_{i}")?;
+        for vertex in polygon.vertices() {
+            writeln!(out, "v {} 0 {}", vertex.x, -vertex.y)?;
+        }
+
+        let count = polygon.vertices().count();
+        let indices: Vec<String> = (next_index..next_index + count)
+            .map(|index| index.to_string())
+            .collect();
+        writeln!(out, "f {}", indices.join(" "))?;
+        next_index += count;
+    }
+
+    Ok(())
+}
+
+/// Which way to turn at a vertex of a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// One step of turn-by-turn directions along a path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Instruction {
+    /// Go straight ahead for this many units
+    Go(f32),
+    /// Turn by this many degrees, between 0 and 180
+    Turn { side: Side, degrees: f32 },
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Go(distance) => write!(f, "go {distance:.0} units"),
+            Instruction::Turn { side, degrees } => {
+                let side = match side {
+                    Side::Left => "left",
+                    Side::Right => "right",
+                };
+                write!(f, "turn {side} {degrees:.0}°")
+            }
+        }
+    }
+}
+
+/// Describes a path as human-readable directions, alternating the length of
+/// each leg with the turn at the vertex that ends it.
+///
+/// Board y grows upwards, so a counterclockwise turn is a left one. Vertices
+/// where the path carries straight on, or repeats a point, add no turn.
+pub fn path_to_instructions(path: &[Point]) -> Vec<Instruction> {
+    let legs: Vec<Vector> = path
+        .windows(2)
+        .map(|pair| {
+            let leg = pair[1] - pair[0];
+            Vector::new(leg.x as f32, leg.y as f32)
+        })
+        .filter(|leg| *leg != Vector::ZERO)
+        .collect();
+
+    let mut instructions = Vec::with_capacity(legs.len() * 2);
+    for (i, leg) in legs.iter().enumerate() {
+        if let Some(previous) = i.checked_sub(1).map(|i| legs[i]) {
+            let degrees = previous.angle_between(*leg).to_degrees();
+            if degrees.round() > 0.0 {
+                let side = if previous.cross(*leg) > 0.0 {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+                instructions.push(Instruction::Turn { side, degrees });
+            }
+        }
+        instructions.push(Instruction::Go(leg.x.hypot(leg.y)));
+    }
+
+    instructions
+}
+
+/// Longest side of the board on the exported PDF page, in points
+#[cfg(feature = "pdf")]
+const PDF_BOARD_SIZE: f32 = 500.0;
+
+/// Space around the board on the exported PDF page, leaving room for labels
+#[cfg(feature = "pdf")]
+const PDF_MARGIN: f32 = 40.0;
+
+/// Writes a single-page vector PDF of the board, its axis ticks and, if
+/// given, a path.
+///
+/// Obstacles are drawn in light gray and the path in green. PDF coordinates
+/// already grow upwards, so unlike [`Board::draw`] no y-flip is needed.
+#[cfg(feature = "pdf")]
+pub fn export_pdf(
+    board: &Board,
+    path: Option<&[Point]>,
+    out: &mut impl std::io::Write,
+) -> std::io::Result<()> {
+    use std::fmt::Write as _;
+
+    let (min_x, min_y, max_x, max_y) = board.bounds();
+    let (min_x, min_y, max_x, max_y): (f32, f32, f32, f32) =
+        (min_x.as_(), min_y.as_(), max_x.as_(), max_y.as_());
+    let scale = PDF_BOARD_SIZE / (max_x - min_x).max(max_y - min_y).max(1.0);
+
+    // Maps board coordinates onto the page
+    let px = |x: f32| PDF_MARGIN + (x - min_x) * scale;
+    let py = |y: f32| PDF_MARGIN + (y - min_y) * scale;
+    let page_width = px(max_x) + PDF_MARGIN;
+    let page_height = py(max_y) + PDF_MARGIN;
+
+    // Writing to a String can't fail, so the fmt results are ignored
+    let mut content = String::new();
+
+    // Boundary
+    let _ = writeln!(
+        content,
+        "0 G 2 w {:.2} {:.2} {:.2} {:.2} re S",
+        px(min_x),
+        py(min_y),
+        (max_x - min_x) * scale,
+        (max_y - min_y) * scale
+    );
+
+    // Tick marks and labels every 50 units, like Board::draw
+    let tick = 2.5 * scale;
+    let _ = writeln!(content, "1 w");
+    for x in ticks(min_x, max_x) {
+        let _ = writeln!(
+            content,
+            "{0:.2} {1:.2} m {0:.2} {2:.2} l S {0:.2} {3:.2} m {0:.2} {4:.2} l S",
+            px(x),
+            py(min_y),
+            py(min_y) + tick,
+            py(max_y),
+            py(max_y) - tick
+        );
+        let _ = writeln!(
+            content,
+            "BT /F1 8 Tf {:.2} {:.2} Td ({}) Tj ET",
+            px(x) - 6.0,
+            py(min_y) - 12.0,
+            x
+        );
+    }
+    for y in ticks(min_y, max_y) {
+        let _ = writeln!(
+            content,
+            "{1:.2} {0:.2} m {2:.2} {0:.2} l S {3:.2} {0:.2} m {4:.2} {0:.2} l S",
+            py(y),
+            px(min_x),
+            px(min_x) + tick,
+            px(max_x),
+            px(max_x) - tick
+        );
+        let _ = writeln!(
+            content,
+            "BT /F1 8 Tf {:.2} {:.2} Td ({}) Tj ET",
+            px(min_x) - 24.0,
+            py(y) - 3.0,
+            y
+        );
+    }
+
+    // Obstacles
+    let _ = writeln!(content, "0.85 g 0.3 G 1 w");
+    for polygon in board.polygons() {
+        for (i, vertex) in polygon.vertices().enumerate() {
+            let op = if i == 0 { "m" } else { "l" };
+            let _ = write!(
+                content,
+                "{:.2} {:.2} {op} ",
+                px(vertex.x as f32),
+                py(vertex.y as f32)
+            );
+        }
+        let _ = writeln!(content, "h B");
+    }
+
+    // Path
+    if let Some(path) = path.filter(|path| path.len() > 1) {
+        let _ = writeln!(content, "0.196 0.804 0.196 RG 2 w");
+        for (i, point) in path.iter().enumerate() {
+            let op = if i == 0 { "m" } else { "l" };
+            let _ = write!(
+                content,
+                "{:.2} {:.2} {op} ",
+                px(point.x as f32),
+                py(point.y as f32)
+            );
+        }
+        let _ = writeln!(content, "S");
+    }
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {page_width:.2} {page_height:.2}] \
+             /Resources << /Font << /F1 5 0 R >> >> /Contents 4 0 R >>"
+        ),
+        format!(
+            "<< /Length {} >>\nstream\n{content}endstream",
+            content.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+    ];
+
+    // Track each object's byte offset for the cross-reference table
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = writeln!(pdf, "{} 0 obj\n{object}\nendobj", i + 1);
+    }
+
+    let xref = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{offset:010} 00000 n ");
+    }
+    let _ = writeln!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF",
+        objects.len() + 1
+    );
+
+    out.write_all(pdf.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polygon;
+
+    #[test]
+    fn test_snap_path_to_grid() {
+        let board = Board::new(vec![Polygon::new(vec![
+            (41, 41).into(),
+            (41, 61).into(),
+            (61, 61).into(),
+            (61, 41).into(),
+        ])]);
+
+        // The middle point would snap to (50, 60), inside the obstacle
+        let path = [
+            Point::new(1.2, 98.7),
+            Point::new(50.0, 64.0),
+            Point::new(99.6, 101.1),
+        ];
+        let snapped = snap_path_to_grid(&board, &path, 10);
+
+        assert_eq!(snapped[0], Point::new(0, 100));
+        assert_eq!(
+            snapped[1],
+            Point::new(50, 64),
+            "Should keep un-snapped point"
+        );
+        assert_eq!(snapped[2], Point::new(100, 100));
+        assert!(
+            board.path_is_valid(&snapped),
+            "Snapped path should be valid"
+        );
+    }
+
+    #[test]
+    fn test_export_obj() {
+        let board = Board::new(vec![
+            Polygon::new(vec![
+                (40, 40).into(),
+                (40, 60).into(),
+                (60, 60).into(),
+                (60, 40).into(),
+            ]),
+            Polygon::new(vec![(0, 0).into(), (10, 0).into(), (5, 10).into()]),
+        ]);
+
+        let mut out = Vec::new();
+        export_obj(&board, &mut out).unwrap();
+        let obj = String::from_utf8(out).unwrap();
+
+        let lines = |prefix: &str| obj.lines().filter(|line| line.starts_with(prefix)).count();
+        assert_eq!(lines("v "), 7);
+        assert_eq!(lines("f "), 2);
+        assert!(obj.contains("v 40 0 -40\n"));
+        assert!(obj.contains("f 1 2 3 4\n"));
+        assert!(obj.ends_with("f 5 6 7\n"));
+    }
+
+    #[test]
+    fn test_path_to_instructions() {
+        // East, then north: a left turn with y growing upwards
+        let path = [Point::new(0, 0), Point::new(30, 0), Point::new(30, 40)];
+        let instructions = path_to_instructions(&path);
+
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0], Instruction::Go(30.0));
+        match instructions[1] {
+            Instruction::Turn { side, degrees } => {
+                assert_eq!(side, Side::Left);
+                assert!((degrees - 90.0).abs() < 1e-3, "Turned {degrees}°");
+            }
+            other => panic!("Expected a turn, got {other:?}"),
+        }
+        assert_eq!(instructions[2], Instruction::Go(40.0));
+        assert_eq!(instructions[1].to_string(), "turn left 90°");
+
+        let reversed: Vec<Point> = path.iter().rev().copied().collect();
+        assert!(matches!(
+            path_to_instructions(&reversed)[1],
+            Instruction::Turn {
+                side: Side::Right,
+                ..
+            }
+        ));
+
+        // Collinear legs carry straight on
+        let straight = [Point::new(0, 0), Point::new(10, 0), Point::new(25, 0)];
+        assert_eq!(
+            path_to_instructions(&straight),
+            [Instruction::Go(10.0), Instruction::Go(15.0)]
+        );
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn test_export_pdf() {
+        let board = crate::board::sample_board();
+        let path = [Point::new(115, 655), Point::new(380, 560)];
+
+        let mut out = Vec::new();
+        export_pdf(&board, Some(&path), &mut out).unwrap();
+
+        assert!(out.starts_with(b"%PDF"), "Output should have a PDF header");
+        assert!(
+            out.ends_with(b"%%EOF\n"),
+            "Output should have a PDF trailer"
+        );
+        assert!(
+            out.len() > 2_000,
+            "Sample board PDF is only {} bytes",
+            out.len()
+        );
+    }
+}
@@ -1,29 +1,61 @@
-use iced::widget::canvas::{self, Cache, Canvas, Event, Geometry};
+use iced::widget::canvas::{
+    self, fill, Cache, Canvas, Event, Frame, Geometry, LineDash, Path, Stroke,
+};
 use iced::widget::{
     button, center, checkbox, column, container, horizontal_space, pick_list, responsive, row,
-    slider, text,
+    slider, stack, text, text_input, Space,
 };
 use iced::Alignment::Center;
 use iced::{event, keyboard, mouse, time, window};
-use iced::{Element, Length, Rectangle, Renderer, Subscription, Task, Theme};
-use search::SearchVariant;
-use std::time::Duration;
+use iced::{Color, Element, Length, Padding, Rectangle, Renderer, Subscription, Task, Theme};
+use search::{SearchOptions, SearchVariant};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
+mod agents;
+mod batch;
 mod board;
+mod bounce;
+mod export;
 mod pathfinder;
 mod point;
 mod polygon;
+mod rng;
+mod scenario;
 mod search;
 mod vector;
 
-pub use board::Board;
-pub use pathfinder::{Heuristic, Pathfinder, SearchState};
-pub use point::Point;
+pub use agents::Agents;
+pub use board::{Board, BoardDiff, BoardError, BoardStats};
+pub use bounce::trace_bounce;
+pub use export::export_obj;
+#[cfg(feature = "pdf")]
+pub use export::export_pdf;
+pub use export::snap_path_to_grid;
+pub use export::visibility_graph_to_json;
+pub use export::{path_to_instructions, Instruction, Side};
+pub use pathfinder::{
+    path_segment_diff, DrawOptions, Heuristic, LandmarkStrategy, MovementModel, OpenSetKind,
+    Pathfinder, RenderMode, SearchState, SearchStatus, SegmentDiff, TieBreak,
+};
+pub use point::{Coordinate, Point};
 pub use polygon::{Edge, Polygon};
-pub use search::Search;
+pub use scenario::Scenario;
+pub use search::{shortest_path, Search};
 pub use vector::Vector;
 
 fn main() -> iced::Result {
+    // `pathfinder batch <dir> ...` runs headless instead of opening a window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|command| command == "batch") {
+        if let Err(error) = batch::run_cli(&args[1..]) {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     iced::application("Pathfinder", App::update, App::view)
         .window(iced::window::Settings {
             min_size: Some((800.0, 600.0).into()),
@@ -35,16 +67,150 @@ fn main() -> iced::Result {
         .run_with(App::new)
 }
 
+/// How far from a vertex, in board units, a click still selects it for
+/// sightlines
+const SIGHTLINE_SNAP_RADIUS: i64 = 15;
+
+/// Where the context menu saves and loads scenarios, relative to the working
+/// directory
+const SCENARIO_FILE: &str = "scenario.json";
+
+/// Time between playback steps
+const PLAYBACK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How long an arrow key must be held before its step starts repeating, so
+/// a tap steps once
+const STEP_REPEAT_DELAY: Duration = Duration::from_millis(300);
+
+/// Number of recent playback steps the step rate readout averages over
+const STEP_RATE_WINDOW: usize = 10;
+
+/// How often the reachability of the goal from the cursor is rechecked
+const REACHABILITY_THROTTLE: Duration = Duration::from_millis(100);
+
+/// Where the visibility graph is exported to
+const GRAPH_FILE: &str = "visibility_graph.json";
+
+/// How many seeds Randomize tries before giving up on finding a board that
+/// leaves both endpoints free
+const RANDOMIZE_ATTEMPTS: u64 = 100;
+
+/// Fraction of the optimal path the trace animation reveals per tick
+const TRACE_STEP: f32 = 0.02;
+
 struct App {
     board_cache: Cache,
+    /// Heuristic field layer, redrawn only when the goal, heuristic or board
+    /// changes
+    heuristic_cache: Cache,
     search_cache: Cache,
     board: Board,
+    /// Seed of the current random board, or `None` for the sample board
+    seed: Option<u64>,
     is_playing: bool,
+    /// When the most recent playback steps happened, oldest first
+    tick_times: VecDeque<Instant>,
     heuristic: Heuristic,
     search: Search,
+    /// True optimal cost for the current board and endpoints, independent of
+    /// the displayed variant
+    optimal_cost: Option<i32>,
+    /// The board, start and goal `optimal_cost` was computed for
+    optimal_cost_for: (Board, Point, Point),
+    /// Summary of the last comparison against the other heuristic
+    heuristic_diff: Option<String>,
+    /// Segments of the current path and the other heuristic's, split by
+    /// [`path_segment_diff`] when the paths were last diffed
+    diff_segments: Option<SegmentDiff>,
+    /// Extra start/goal pairs planned independently alongside the main search
+    agents: Agents,
     start: Point,
     goal: Point,
     show_solution: bool,
+    show_search_tree: bool,
+    render_mode: RenderMode,
+    fog_of_war: bool,
+    size_by_g_score: bool,
+    expansion_gradient: bool,
+    precise_cost: bool,
+    show_axes: bool,
+    /// Draw y growing downward, for boards in screen coordinates
+    y_down: bool,
+    fill_rule: fill::Rule,
+    show_corridor: bool,
+    show_heuristic_field: bool,
+    show_straight_line: bool,
+    /// Whether the visibility graph keeps only edges along common tangents
+    reduced_graph: bool,
+    /// Whether visibility graph playback steps through each relaxed edge
+    edge_granularity: bool,
+    /// Width of the band drawn along the path when `show_corridor` is set
+    corridor_width: f32,
+    show_recent_edges: bool,
+    /// How many of the latest expansions' edges to draw when
+    /// `show_recent_edges` is set
+    recent_edges_window: f32,
+    /// Heuristic weight for weighted A*, where 1.0 is plain A*
+    weight: f32,
+    /// Multiplier for stroke widths and circle radii on the canvas
+    line_scale: f32,
+    follow_mouse: bool,
+    /// Whether to show if the goal can be reached from the cursor
+    show_reachability: bool,
+    /// Board point under the cursor, while showing reachability
+    hover: Option<Point>,
+    /// Whether the goal was reachable from `hover`, along with the goal it
+    /// was checked against
+    hover_reachable: Option<(Point, bool)>,
+    /// Latest cursor position awaiting a replan while following the mouse
+    pending_goal: Option<Point>,
+    context_menu: Option<ContextMenu>,
+    /// Anchor of the ruler drawn to the cursor, if measuring
+    measure_from: Option<Point>,
+    /// Outcome of the last scenario save or load
+    notice: Option<String>,
+    /// Whether clicks pick a vertex to show sightlines from, instead of the
+    /// start
+    sightline_mode: bool,
+    sightline_vertex: Option<Point>,
+    /// How much of the optimal path the trace animation has drawn, from 0.0
+    /// to 1.0, or `None` when it isn't shown
+    trace: Option<f32>,
+    is_tracing: bool,
+    /// Cost up to which the finished search's open and closed vertices are
+    /// shown instead of the current step's, or `None` when stepping by node
+    cost_threshold: Option<f32>,
+    /// Step number typed into the box next to the slider
+    step_input: String,
+    /// The other variant, played back in lockstep with the main search
+    comparison: Option<Search>,
+    /// Fraction of the way through both searches while comparing
+    progress: f32,
+    /// Arrow keys currently held down, most recently pressed last
+    held_steps: Vec<StepDirection>,
+    /// When the most recently held arrow key went down
+    held_since: Option<Instant>,
+    /// How often a held arrow key repeats its step, once
+    /// [`STEP_REPEAT_DELAY`] has passed
+    step_repeat: Duration,
+}
+
+/// Which way an arrow key steps through the search history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StepDirection {
+    Back,
+    Next,
+}
+
+/// Menu opened by right-clicking the canvas
+#[derive(Debug, Clone, Copy)]
+struct ContextMenu {
+    /// Where the click happened, relative to the canvas
+    position: iced::Point,
+    /// The clicked point in board coordinates
+    point: Point,
+    /// Index of the obstacle that was clicked, if any
+    polygon: Option<usize>,
 }
 
 impl Default for App {
@@ -54,17 +220,69 @@ impl Default for App {
         let heuristic = Heuristic::default();
         let goal = Point::new(380, 560);
         let search = Search::new(board.clone(), start, goal, heuristic);
+        let optimal_cost = search::shortest_path_cost(&board, start, goal);
 
         Self {
             board_cache: Cache::default(),
+            heuristic_cache: Cache::default(),
             search_cache: Cache::default(),
             heuristic,
             start,
             goal,
             search,
+            optimal_cost,
+            optimal_cost_for: (board.clone(), start, goal),
+            heuristic_diff: None,
+            diff_segments: None,
+            agents: Agents::new(
+                board.clone(),
+                &[],
+                heuristic,
+                SearchVariant::VisibilityGraph,
+            ),
             board,
+            seed: None,
             is_playing: false,
+            tick_times: VecDeque::new(),
             show_solution: false,
+            show_search_tree: false,
+            render_mode: RenderMode::default(),
+            fog_of_war: false,
+            size_by_g_score: false,
+            expansion_gradient: false,
+            precise_cost: false,
+            show_axes: true,
+            y_down: false,
+            fill_rule: fill::Rule::NonZero,
+            show_corridor: false,
+            show_heuristic_field: false,
+            show_straight_line: false,
+            reduced_graph: false,
+            edge_granularity: false,
+            corridor_width: 10.0,
+            show_recent_edges: false,
+            recent_edges_window: 5.0,
+            weight: 1.0,
+            line_scale: 1.0,
+            follow_mouse: false,
+            show_reachability: false,
+            hover: None,
+            hover_reachable: None,
+            pending_goal: None,
+            context_menu: None,
+            measure_from: None,
+            notice: None,
+            sightline_mode: false,
+            sightline_vertex: None,
+            trace: None,
+            is_tracing: false,
+            cost_threshold: None,
+            step_input: String::new(),
+            comparison: None,
+            progress: 0.0,
+            held_steps: Vec::new(),
+            held_since: None,
+            step_repeat: Duration::from_millis(50),
         }
     }
 }
@@ -76,6 +294,27 @@ enum Message {
 
     TogglePlay,
     ToggleSolution,
+    ToggleSearchTree,
+    ToggleCleanRender,
+    ToggleFocusRender,
+    ToggleFogOfWar,
+    ToggleSizeByGScore,
+    ToggleExpansionGradient,
+    TogglePreciseCost,
+    ToggleAxes,
+    ToggleYDown,
+    ToggleFillRule,
+    ToggleCorridor,
+    ToggleHeuristicField,
+    ToggleStraightLine,
+    SetCorridorWidth(f32),
+    ToggleRecentEdges,
+    SetRecentEdgesWindow(f32),
+    ToggleFollowMouse,
+    TrackCursor(Point),
+    ToggleReachability,
+    CheckReachability,
+    Replan,
     PickHeuristic(Heuristic),
     PickVariant(SearchVariant),
     SetStart(Point),
@@ -83,9 +322,44 @@ enum Message {
     Tick,
     Back,
     Next,
+    HoldStep(StepDirection),
+    ReleaseStep(StepDirection),
+    RepeatStep,
+    SetStepRepeat(f32),
     Reset,
     Finish,
     JumpTo(f32),
+    JumpToInput(String),
+    JumpToGoalDiscovery,
+    PrevReopening,
+    NextReopening,
+    DiffHeuristics,
+    OpenContextMenu(iced::Point, Point),
+    CloseContextMenu,
+    AddObstacle(Point),
+    DeleteObstacle(usize),
+    ToggleObstacle(usize),
+    MeasureFrom(Option<Point>),
+    SaveScenario,
+    LoadScenario(PathBuf),
+    ExportGraph,
+    ToggleSightlines,
+    ToggleReducedGraph,
+    ToggleEdgeGranularity,
+    TracePath,
+    SetTrace(f32),
+    TraceTick,
+    ToggleCostFrontier,
+    SetCostThreshold(f32),
+    ToggleComparison,
+    ForkSearch,
+    SetProgress(f32),
+    PickSightlineVertex(Point),
+    SetLineScale(f32),
+    SetWeight(f32),
+    Randomize,
+    AddAgent,
+    ClearAgents,
 }
 
 impl App {
@@ -98,6 +372,12 @@ impl App {
     }
 
     fn slide(&self) -> Element<'_, Message> {
+        if self.comparison.is_some() {
+            return slider(0.0..=1.0, self.progress, Message::SetProgress)
+                .step(0.001)
+                .width(Length::Fill)
+                .into();
+        }
         slider(
             0.0..=self.search.total_steps() as f32,
             self.search.current_step() as f32,
@@ -107,23 +387,87 @@ impl App {
         .into()
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         center(
             column![
-                pick_list(
-                    SearchVariant::ALL,
-                    Some(self.search.variant()),
-                    Message::PickVariant
-                ),
+                row![
+                    pick_list(
+                        SearchVariant::ALL,
+                        Some(self.search.variant()),
+                        Message::PickVariant
+                    ),
+                    button(text("Randomize").size(12))
+                        .style(style::control)
+                        .on_press(Message::Randomize),
+                    button(text("Add Agent").size(12))
+                        .style(style::control)
+                        .on_press(Message::AddAgent),
+                    button(text("Clear Agents").size(12))
+                        .style(style::control)
+                        .on_press_maybe((!self.agents.is_empty()).then_some(Message::ClearAgents)),
+                    text(self.seed.map_or_else(
+                        || "Sample board".to_string(),
+                        |seed| format!("Seed: {seed}")
+                    ))
+                    .size(12),
+                    text(self.board.stats().to_string()).size(12),
+                    text(self.cost_summary()).size(12),
+                    text(self.step_time_summary()).size(12),
+                    text(self.step_rate_summary()).size(12),
+                    text(self.comparison_summary()).size(12),
+                    text(self.notice.clone().unwrap_or_default()).size(12),
+                    horizontal_space(),
+                    checkbox("Corridor", self.show_corridor)
+                        .on_toggle(|_| Message::ToggleCorridor)
+                        .size(12)
+                        .text_size(12),
+                    slider(1.0..=40.0, self.corridor_width, Message::SetCorridorWidth)
+                        .step(1.0)
+                        .width(Length::Fixed(80.0)),
+                    checkbox("Recent Edges", self.show_recent_edges)
+                        .on_toggle(|_| Message::ToggleRecentEdges)
+                        .size(12)
+                        .text_size(12),
+                    slider(
+                        1.0..=50.0,
+                        self.recent_edges_window,
+                        Message::SetRecentEdgesWindow
+                    )
+                    .step(1.0)
+                    .width(Length::Fixed(80.0)),
+                    text("Key repeat:").size(12),
+                    slider(
+                        20.0..=200.0,
+                        self.step_repeat.as_millis() as f32,
+                        Message::SetStepRepeat
+                    )
+                    .step(10.0)
+                    .width(Length::Fixed(80.0)),
+                    text("Line weight:").size(12),
+                    slider(0.5..=3.0, self.line_scale, Message::SetLineScale)
+                        .step(0.25)
+                        .width(Length::Fixed(120.0)),
+                ]
+                .spacing(10)
+                .align_y(Center),
                 responsive(move |size| {
-                    center(
+                    center(stack![
                         Canvas::new(self)
                             .width(Length::Fixed(size.width))
                             .height(Length::Fixed(size.height)),
-                    )
+                        self.context_menu(),
+                    ])
                     .into()
                 }),
-                self.slide(),
+                row![
+                    self.slide(),
+                    text_input("Step", &self.step_input)
+                        .on_input(Message::JumpToInput)
+                        .size(12)
+                        .width(Length::Fixed(60.0)),
+                ]
+                .spacing(5)
+                .align_y(Center),
                 self.controls(),
             ]
             .align_x(Center)
@@ -135,16 +479,411 @@ impl App {
     }
 
     fn renew_search(&mut self, variant: SearchVariant) {
-        self.search = Search::new_for_variant(
+        if !variant.supports(self.heuristic) {
+            self.heuristic = Heuristic::default();
+        }
+        // Only the visibility graph has sightlines to show
+        if variant != SearchVariant::VisibilityGraph {
+            self.sightline_mode = false;
+            self.sightline_vertex = None;
+        }
+        self.search = Search::new_with_options(
             self.board.clone(),
             self.start,
             self.goal,
             self.heuristic,
             variant,
+            self.search_options(),
         );
+        self.refresh_optimal_cost();
+        self.heuristic_diff = None;
+        self.diff_segments = None;
+        self.trace = None;
+        self.is_tracing = false;
+        self.renew_agents(&self.agents.pairs());
+        self.renew_comparison();
+    }
+
+    /// The settings new searches are built with
+    fn search_options(&self) -> SearchOptions {
+        SearchOptions {
+            weight: self.weight,
+            reduced_graph: self.reduced_graph,
+            edge_granularity: self.edge_granularity,
+        }
+    }
+
+    /// Reruns the comparison search, if any, to match the main search
+    fn renew_comparison(&mut self) {
+        if self.comparison.is_some() {
+            self.comparison = Some(self.comparison_search());
+        }
+    }
+
+    /// Runs the other variant with the main search's settings, at the same
+    /// progress
+    fn comparison_search(&mut self) -> Search {
+        let mut comparison = Search::new_with_options(
+            self.board.clone(),
+            self.start,
+            self.goal,
+            self.heuristic,
+            self.search.variant().other(),
+            self.search_options(),
+        );
+        self.progress = self.search.progress();
+        comparison.jump_to_progress(self.progress);
+        comparison
+    }
+
+    /// Moves the comparison search to the main search's progress, unless the
+    /// main search is already where the progress slider put it
+    fn sync_comparison(&mut self) {
+        let Some(comparison) = &mut self.comparison else {
+            return;
+        };
+        let expected = (self.progress * self.search.total_steps() as f32).round() as usize;
+        if self.search.current_step() != expected {
+            self.progress = self.search.progress();
+            comparison.jump_to_progress(self.progress);
+        }
+    }
+
+    /// Captures the board, endpoints and settings needed to reproduce the
+    /// current view
+    fn scenario(&self) -> Scenario {
+        Scenario {
+            board: self.board.clone(),
+            start: self.start,
+            goal: self.goal,
+            variant: self.search.variant(),
+            heuristic: self.heuristic,
+            show_solution: self.show_solution,
+        }
+    }
+
+    /// Replaces the board, endpoints and settings with a scenario's and
+    /// rebuilds the search from scratch
+    fn load_scenario(&mut self, scenario: Scenario) {
+        self.is_playing = false;
+        self.board = scenario.board;
+        self.seed = None;
+        self.start = scenario.start;
+        self.goal = scenario.goal;
+        self.heuristic = scenario.heuristic;
+        self.show_solution = scenario.show_solution;
+        self.renew_search(scenario.variant);
+        self.sightline_vertex = None;
+        self.measure_from = None;
+        self.board_cache.clear();
+        self.heuristic_cache.clear();
+        self.search_cache.clear();
+    }
+
+    /// Pauses playback and steps the search once
+    fn step(&mut self, direction: StepDirection) {
+        self.is_playing = false;
+        match direction {
+            StepDirection::Back => self.search.step_back(),
+            StepDirection::Next => self.search.step_forward(),
+        };
+        self.search_cache.clear();
+    }
+
+    /// Replans every agent for the current board, heuristic and variant
+    fn renew_agents(&mut self, pairs: &[(Point, Point)]) {
+        self.agents = Agents::new(
+            self.board.clone(),
+            pairs,
+            self.heuristic,
+            self.search.variant(),
+        );
+    }
+
+    /// Recomputes the reference optimal cost, unless the board and endpoints
+    /// are still the ones it was computed for
+    fn refresh_optimal_cost(&mut self) {
+        let (board, start, goal) = &self.optimal_cost_for;
+        if (board, *start, *goal) == (&self.board, self.start, self.goal) {
+            return;
+        }
+        self.optimal_cost = search::shortest_path_cost(&self.board, self.start, self.goal);
+        self.optimal_cost_for = (self.board.clone(), self.start, self.goal);
+    }
+
+    /// Reruns the current search after the endpoints or heuristic changed
+    fn rebuild_search(&mut self) {
+        self.search
+            .rebuild(self.board.clone(), self.start, self.goal, self.heuristic);
+        self.refresh_optimal_cost();
+        self.heuristic_diff = None;
+        self.diff_segments = None;
+        self.trace = None;
+        self.is_tracing = false;
+        self.renew_comparison();
+    }
+
+    /// The highest g-score reached by the finished search
+    fn max_g_score(&self) -> f32 {
+        self.search
+            .history()
+            .last()
+            .into_iter()
+            .flat_map(|state| state.g_scores.values())
+            .map(|&g| g as f32)
+            .fold(0.0, f32::max)
+    }
+
+    /// The last reopening before the current step, if any
+    fn prev_reopening(&self) -> Option<usize> {
+        let current = self.search.current_step();
+        self.search
+            .reopening_steps()
+            .iter()
+            .rev()
+            .find(|&&step| step < current)
+            .copied()
+    }
+
+    /// The first reopening after the current step, if any
+    fn next_reopening(&self) -> Option<usize> {
+        let current = self.search.current_step();
+        self.search
+            .reopening_steps()
+            .iter()
+            .find(|&&step| step > current)
+            .copied()
+    }
+
+    /// Draws green lines from the selected vertex to every vertex it can see,
+    /// and red lines towards the others up to where they are blocked
+    fn draw_sightlines(&self, frame: &mut Frame) {
+        let Some(vertex) = self.sightline_vertex else {
+            return;
+        };
+        let Some(sightlines) = self.search.sightlines(vertex) else {
+            return;
+        };
+
+        for sightline in sightlines {
+            let (end, color) = match sightline.blocked_at {
+                None => (sightline.to, Color::from_rgb8(34, 139, 34)),
+                Some(blocked_at) => (blocked_at, Color::from_rgb8(220, 20, 60)),
+            };
+            frame.stroke(
+                &Path::line(
+                    vertex.to_canvas_with(self.y_down),
+                    end.to_canvas_with(self.y_down),
+                ),
+                Stroke::default()
+                    .with_color(color)
+                    .with_width(self.line_scale),
+            );
+        }
+        frame.fill(
+            &Path::circle(vertex.to_canvas_with(self.y_down), 3.0 * self.line_scale),
+            Color::BLACK,
+        );
+    }
+
+    /// Draws the direct segment from start to goal, ignoring obstacles, with
+    /// the detour factor of the optimal path at its midpoint
+    fn draw_straight_line(&self, frame: &mut Frame) {
+        if !self.show_straight_line {
+            return;
+        }
+
+        let (start, goal) = (
+            self.start.to_canvas_with(self.y_down),
+            self.goal.to_canvas_with(self.y_down),
+        );
+        frame.stroke(
+            &Path::line(start, goal),
+            Stroke {
+                line_dash: LineDash {
+                    segments: &[4.0, 4.0],
+                    offset: 0,
+                },
+                ..Default::default()
+            }
+            .with_color(Color::from_rgb8(105, 105, 105))
+            .with_width(self.line_scale),
+        );
+
+        let label = self
+            .search
+            .detour_factor()
+            .map_or_else(|| "no path".to_string(), |detour| format!("×{detour:.3}"));
+        frame.fill_text(canvas::Text {
+            content: label,
+            position: iced::Point::new((start.x + goal.x) / 2.0, (start.y + goal.y) / 2.0)
+                + iced::Vector::new(3.0, -3.0),
+            color: Color::BLACK,
+            size: 5.0.into(),
+            ..canvas::Text::default()
+        });
+    }
+
+    /// Draws the part of the optimal path revealed so far by the trace
+    /// animation, with a dot at its tip
+    fn draw_trace(&self, frame: &mut Frame) {
+        let (Some(progress), Some((path, _))) = (self.trace, self.search.get_optimal_path()) else {
+            return;
+        };
+
+        let length = Search::path_length_f64(path) * f64::from(progress);
+        let prefix = Search::path_prefix(path, length);
+        let color = Color::from_rgb8(255, 140, 0);
+
+        for window in prefix.windows(2) {
+            frame.stroke(
+                &Path::line(
+                    window[0].to_canvas_with(self.y_down),
+                    window[1].to_canvas_with(self.y_down),
+                ),
+                Stroke::default()
+                    .with_color(color)
+                    .with_width(3.0 * self.line_scale),
+            );
+        }
+        if let Some(tip) = prefix.last() {
+            frame.fill(
+                &Path::circle(tip.to_canvas_with(self.y_down), 3.0 * self.line_scale),
+                color,
+            );
+        }
+    }
+
+    /// Shows both variants' absolute steps while comparing them
+    fn comparison_summary(&self) -> String {
+        let Some(comparison) = &self.comparison else {
+            return String::new();
+        };
+        [&self.search, comparison]
+            .map(|search| {
+                format!(
+                    "{}: {}/{}",
+                    search,
+                    search.current_step(),
+                    search.total_steps()
+                )
+            })
+            .join(" · ")
+    }
+
+    /// Overlays the comparison search's closed vertices and current best path
+    /// in its own color
+    fn draw_comparison(&self, frame: &mut Frame) {
+        let Some(comparison) = &self.comparison else {
+            return;
+        };
+
+        let color = Color::from_rgb8(148, 0, 211);
+        let state = comparison.get_state();
+        for vertex in &state.closed {
+            frame.stroke(
+                &Path::circle(vertex.to_canvas_with(self.y_down), 4.0 * self.line_scale),
+                Stroke::default()
+                    .with_color(color)
+                    .with_width(self.line_scale),
+            );
+        }
+
+        let path = state.best_path.as_ref().or_else(|| {
+            state
+                .next_vertex
+                .and_then(|vertex| state.current_paths.get(&vertex))
+        });
+        for window in path.into_iter().flat_map(|path| path.windows(2)) {
+            frame.stroke(
+                &Path::line(
+                    window[0].to_canvas_with(self.y_down),
+                    window[1].to_canvas_with(self.y_down),
+                ),
+                Stroke {
+                    line_dash: LineDash {
+                        segments: &[4.0, 4.0],
+                        offset: 0,
+                    },
+                    ..Default::default()
+                }
+                .with_color(color)
+                .with_width(2.0 * self.line_scale),
+            );
+        }
+    }
+
+    /// Overlays the paths last diffed, with shared segments in gray and
+    /// segments only on the current path or only on the other heuristic's in
+    /// blue and orange
+    fn draw_segment_diff(&self, frame: &mut Frame) {
+        let Some((shared, only_current, only_other)) = &self.diff_segments else {
+            return;
+        };
+
+        let groups = [
+            (shared, Color::from_rgb8(90, 90, 90)),
+            (only_current, Color::from_rgb8(30, 110, 230)),
+            (only_other, Color::from_rgb8(240, 140, 0)),
+        ];
+        for (segments, color) in groups {
+            for segment in segments {
+                frame.stroke(
+                    &Path::line(
+                        segment.start.to_canvas_with(self.y_down),
+                        segment.end.to_canvas_with(self.y_down),
+                    ),
+                    Stroke::default()
+                        .with_color(color)
+                        .with_width(4.0 * self.line_scale),
+                );
+            }
+        }
+    }
+
+    /// Summarizes the current path cost against the true optimum
+    fn cost_summary(&self) -> String {
+        match (self.search.optimal_path_score(), self.optimal_cost) {
+            (Some(cost), Some(optimal)) => {
+                let ratio = self.search.cost_ratio(optimal).unwrap_or(1.0);
+                format!("Cost: {cost} (optimal {optimal}, ratio {ratio:.3})")
+            }
+            _ => "No path".to_string(),
+        }
+    }
+
+    /// Playback steps per second achieved over the last few steps, against
+    /// the configured rate
+    fn step_rate_summary(&self) -> String {
+        let target = 1.0 / PLAYBACK_INTERVAL.as_secs_f64();
+        let rate = match (self.tick_times.front(), self.tick_times.back()) {
+            (Some(first), Some(last)) if self.is_playing && last > first => {
+                let elapsed = last.duration_since(*first).as_secs_f64();
+                format!("{:.1}", (self.tick_times.len() - 1) as f64 / elapsed)
+            }
+            _ => "—".to_string(),
+        };
+        format!("Steps/s: {rate} of {target:.1}")
+    }
+
+    /// Time the search spent on the successors of the current step's
+    /// expansion, if it expanded a vertex
+    fn step_time_summary(&self) -> String {
+        self.search
+            .step_timings()
+            .get(self.search.current_step())
+            .map(|time| format!("Step time: {time:?}"))
+            .unwrap_or_default()
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
+        let task = self.handle(message);
+        // Whatever moved the main search, the comparison follows
+        self.sync_comparison();
+        task
+    }
+
+    fn handle(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::ToggleFullscreen => toggle_fullscreen(),
             Message::ChangeMode(mode) => {
@@ -152,6 +891,7 @@ impl App {
             }
             Message::TogglePlay => {
                 self.is_playing = !self.is_playing;
+                self.tick_times.clear();
                 Task::none()
             }
             Message::ToggleSolution => {
@@ -159,23 +899,234 @@ impl App {
                 self.search_cache.clear();
                 Task::none()
             }
+            Message::ToggleSearchTree => {
+                self.show_search_tree = !self.show_search_tree;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleCleanRender => {
+                self.render_mode = match self.render_mode {
+                    RenderMode::Clean => RenderMode::Full,
+                    _ => RenderMode::Clean,
+                };
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleFocusRender => {
+                self.render_mode = match self.render_mode {
+                    RenderMode::Focus => RenderMode::Full,
+                    _ => RenderMode::Focus,
+                };
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleFogOfWar => {
+                self.fog_of_war = !self.fog_of_war;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleSizeByGScore => {
+                self.size_by_g_score = !self.size_by_g_score;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleExpansionGradient => {
+                self.expansion_gradient = !self.expansion_gradient;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::TogglePreciseCost => {
+                self.precise_cost = !self.precise_cost;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleAxes => {
+                self.show_axes = !self.show_axes;
+                self.board_cache.clear();
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleFillRule => {
+                self.fill_rule = match self.fill_rule {
+                    fill::Rule::NonZero => fill::Rule::EvenOdd,
+                    fill::Rule::EvenOdd => fill::Rule::NonZero,
+                };
+                self.board_cache.clear();
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleCorridor => {
+                self.show_corridor = !self.show_corridor;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::TracePath => {
+                self.trace = Some(0.0);
+                self.is_tracing = true;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::SetTrace(progress) => {
+                self.trace = Some(progress);
+                self.is_tracing = false;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::TraceTick => {
+                let progress = self.trace.unwrap_or_default() + TRACE_STEP;
+                self.trace = Some(progress.min(1.0));
+                self.is_tracing = progress < 1.0;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleCostFrontier => {
+                self.cost_threshold = match self.cost_threshold {
+                    Some(_) => None,
+                    None => Some(self.max_g_score() / 2.0),
+                };
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::SetCostThreshold(threshold) => {
+                self.cost_threshold = Some(threshold);
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleReducedGraph => {
+                self.is_playing = false;
+                self.reduced_graph = !self.reduced_graph;
+                self.renew_search(self.search.variant());
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleEdgeGranularity => {
+                self.is_playing = false;
+                self.edge_granularity = !self.edge_granularity;
+                self.renew_search(self.search.variant());
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleSightlines => {
+                self.sightline_mode = !self.sightline_mode;
+                self.sightline_vertex = None;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::PickSightlineVertex(point) => {
+                // Snap to the nearest vertex, or deselect if none is close
+                let squared_distance = |v: &Point| {
+                    let (dx, dy) = ((v.x - point.x) as i64, (v.y - point.y) as i64);
+                    dx * dx + dy * dy
+                };
+                self.sightline_vertex = self
+                    .board
+                    .vertices()
+                    .into_iter()
+                    .chain([self.start, self.goal])
+                    .filter(|v| squared_distance(v) <= SIGHTLINE_SNAP_RADIUS.pow(2))
+                    .min_by_key(squared_distance);
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleStraightLine => {
+                self.show_straight_line = !self.show_straight_line;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleYDown => {
+                self.y_down = !self.y_down;
+                self.board_cache.clear();
+                self.heuristic_cache.clear();
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleHeuristicField => {
+                self.show_heuristic_field = !self.show_heuristic_field;
+                self.heuristic_cache.clear();
+                Task::none()
+            }
+            Message::SetCorridorWidth(width) => {
+                self.corridor_width = width;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleRecentEdges => {
+                self.show_recent_edges = !self.show_recent_edges;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::SetRecentEdgesWindow(window) => {
+                self.recent_edges_window = window;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleFollowMouse => {
+                self.follow_mouse = !self.follow_mouse;
+                self.pending_goal = None;
+                Task::none()
+            }
+            Message::TrackCursor(point) => {
+                // Goals inside obstacles are unreachable, so keep the last one
+                if self.follow_mouse && !self.board.contains_point(&point) {
+                    self.pending_goal = Some(point);
+                }
+                if self.show_reachability && self.hover != Some(point) {
+                    self.hover = Some(point);
+                    // Inside an obstacle is cheap to rule out right away, the
+                    // rest waits for the throttled check
+                    self.hover_reachable = self
+                        .board
+                        .contains_point(&point)
+                        .then_some((self.goal, false));
+                }
+                Task::none()
+            }
+            Message::ToggleReachability => {
+                self.show_reachability = !self.show_reachability;
+                self.hover = None;
+                self.hover_reachable = None;
+                Task::none()
+            }
+            Message::CheckReachability => {
+                if let Some(point) = self.hover {
+                    let reachable = self.board.is_reachable(&point, &self.goal);
+                    self.hover_reachable = Some((self.goal, reachable));
+                }
+                Task::none()
+            }
+            Message::Replan => {
+                if let Some(goal) = self.pending_goal.take() {
+                    self.is_playing = false;
+                    self.goal = goal;
+                    self.rebuild_search();
+                    self.search.jump_to(self.search.total_steps());
+                    self.search_cache.clear();
+                    self.heuristic_cache.clear();
+                }
+                Task::none()
+            }
             Message::PickHeuristic(heuristic) => {
                 self.is_playing = false;
                 self.heuristic = heuristic;
-                self.renew_search(self.search.variant());
+                self.rebuild_search();
+                self.renew_agents(&self.agents.pairs());
                 self.search_cache.clear();
+                self.heuristic_cache.clear();
                 Task::none()
             }
             Message::PickVariant(variant) => {
                 self.is_playing = false;
                 self.renew_search(variant);
                 self.search_cache.clear();
+                // The new variant may not support the heuristic
+                self.heuristic_cache.clear();
                 Task::none()
             }
             Message::SetStart(start) => {
+                self.context_menu = None;
                 let is_finished = self.search.is_finished();
                 self.start = start;
-                self.renew_search(self.search.variant());
+                self.rebuild_search();
                 if is_finished {
                     self.search.jump_to(self.search.total_steps());
                 }
@@ -183,43 +1134,100 @@ impl App {
                 Task::none()
             }
             Message::SetGoal(goal) => {
+                self.context_menu = None;
                 let is_finished = self.search.is_finished();
                 self.goal = goal;
-                self.renew_search(self.search.variant());
+                self.rebuild_search();
                 if is_finished {
                     self.search.jump_to(self.search.total_steps());
                 }
                 self.search_cache.clear();
+                self.heuristic_cache.clear();
                 Task::none()
             }
             Message::Tick => {
                 if self.is_playing {
-                    if !self.search.step_forward() {
+                    if self.tick_times.len() == STEP_RATE_WINDOW {
+                        self.tick_times.pop_front();
+                    }
+                    self.tick_times.push_back(Instant::now());
+                    self.search.step_forward();
+                    if self.search.is_finished() {
                         self.is_playing = false;
-                        let all_path_points = self.search.get_optimal_path().unwrap();
-                        // eprintln!(
-                        //     "Search finished! {}",
-                        //     all_path_points
-                        //         .0
-                        //         .iter()
-                        //         .map(|p| format!("({},{})", p.x, p.y))
-                        //         .collect::<Vec<_>>()
-                        //         .join(" -> ")
-                        // );
+                        if !self.search.found_path() {
+                            self.notice = Some("No path to the goal".to_string());
+                        }
                     }
                     self.search_cache.clear();
                 }
                 Task::none()
             }
             Message::Back => {
-                self.is_playing = false;
-                self.search.step_back();
-                self.search_cache.clear();
+                self.step(StepDirection::Back);
                 Task::none()
             }
             Message::Next => {
+                self.step(StepDirection::Next);
+                Task::none()
+            }
+            Message::HoldStep(direction) => {
+                // The OS repeats key presses while held; the timer handles that
+                if !self.held_steps.contains(&direction) {
+                    self.held_steps.push(direction);
+                    self.held_since = Some(Instant::now());
+                    self.step(direction);
+                }
+                Task::none()
+            }
+            Message::ReleaseStep(direction) => {
+                // Any other arrow still held takes over
+                self.held_steps.retain(|&held| held != direction);
+                Task::none()
+            }
+            Message::RepeatStep => {
+                let repeating = self
+                    .held_since
+                    .is_some_and(|since| since.elapsed() >= STEP_REPEAT_DELAY);
+                if let (true, Some(&direction)) = (repeating, self.held_steps.last()) {
+                    self.step(direction);
+                }
+                Task::none()
+            }
+            Message::SetStepRepeat(millis) => {
+                self.step_repeat = Duration::from_millis(millis as u64);
+                Task::none()
+            }
+            Message::JumpToInput(input) => {
+                // Anything but digits is ignored, leaving the box as it was
+                if !input.chars().all(|c| c.is_ascii_digit()) {
+                    return Task::none();
+                }
+                let step = input.parse::<usize>().ok();
+                self.step_input = input;
+                match step {
+                    Some(step) => {
+                        self.is_playing = false;
+                        let step = step.min(self.search.total_steps());
+                        Task::done(Message::JumpTo(step as f32))
+                    }
+                    None => Task::none(),
+                }
+            }
+            Message::ToggleComparison => {
                 self.is_playing = false;
-                self.search.step_forward();
+                self.comparison = match self.comparison {
+                    Some(_) => None,
+                    None => Some(self.comparison_search()),
+                };
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::SetProgress(progress) => {
+                self.progress = progress;
+                self.search.jump_to_progress(progress);
+                if let Some(comparison) = &mut self.comparison {
+                    comparison.jump_to_progress(progress);
+                }
                 self.search_cache.clear();
                 Task::none()
             }
@@ -228,11 +1236,204 @@ impl App {
                 self.search_cache.clear();
                 Task::none()
             }
+            Message::AddAgent => {
+                let mut pairs = self.agents.pairs();
+                pairs.push((self.start, self.goal));
+                self.renew_agents(&pairs);
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ClearAgents => {
+                self.renew_agents(&[]);
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::Randomize => {
+                // Re-roll until neither endpoint is stuck inside an obstacle
+                let first = self.seed.map_or(0, |seed| seed + 1);
+                let Some((seed, board)) = (first..first + RANDOMIZE_ATTEMPTS)
+                    .map(|seed| (seed, Board::random(seed)))
+                    .find(|(_, board)| {
+                        !board.contains_point(&self.start) && !board.contains_point(&self.goal)
+                    })
+                else {
+                    self.notice = Some(format!(
+                        "No random board in {RANDOMIZE_ATTEMPTS} tries left the start and goal free"
+                    ));
+                    return Task::none();
+                };
+
+                self.seed = Some(seed);
+                self.board = board;
+                self.renew_search(self.search.variant());
+                self.sightline_vertex = None;
+                self.board_cache.clear();
+                self.heuristic_cache.clear();
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::SaveScenario => {
+                self.context_menu = None;
+                self.notice = Some(
+                    match std::fs::write(SCENARIO_FILE, self.scenario().to_json()) {
+                        Ok(()) => format!("Saved {SCENARIO_FILE}"),
+                        Err(error) => format!("Couldn't save {SCENARIO_FILE}: {error}"),
+                    },
+                );
+                Task::none()
+            }
+            Message::ExportGraph => {
+                self.context_menu = None;
+                if let Some(graph) = self.search.visibility_graph() {
+                    self.notice = Some(
+                        match std::fs::write(GRAPH_FILE, visibility_graph_to_json(graph)) {
+                            Ok(()) => format!("Saved {GRAPH_FILE}"),
+                            Err(error) => format!("Couldn't save {GRAPH_FILE}: {error}"),
+                        },
+                    );
+                }
+                Task::none()
+            }
+            Message::LoadScenario(path) => {
+                self.context_menu = None;
+                let scenario = std::fs::read_to_string(&path)
+                    .map_err(|error| error.to_string())
+                    .and_then(|json| Scenario::from_json(&json).map_err(|e| e.to_string()));
+                self.notice = Some(match scenario {
+                    Ok(scenario) => {
+                        self.load_scenario(scenario);
+                        format!("Loaded {}", path.display())
+                    }
+                    Err(error) => format!("Couldn't load {}: {error}", path.display()),
+                });
+                Task::none()
+            }
+            Message::OpenContextMenu(position, point) => {
+                self.context_menu = Some(ContextMenu {
+                    position,
+                    point,
+                    polygon: self.board.polygon_at(&point),
+                });
+                Task::none()
+            }
+            Message::CloseContextMenu => {
+                self.context_menu = None;
+                Task::none()
+            }
+            Message::AddObstacle(center) => {
+                const HALF_SIZE: i32 = 10;
+                self.context_menu = None;
+                let square = Polygon::new(vec![
+                    Point::new(center.x - HALF_SIZE, center.y - HALF_SIZE),
+                    Point::new(center.x - HALF_SIZE, center.y + HALF_SIZE),
+                    Point::new(center.x + HALF_SIZE, center.y + HALF_SIZE),
+                    Point::new(center.x + HALF_SIZE, center.y - HALF_SIZE),
+                ]);
+                // Leave the board untouched rather than let obstacles overlap
+                if let Err(error) = self.board.try_add_polygon(square) {
+                    self.notice = Some(format!("Couldn't add obstacle: {error}"));
+                    return Task::none();
+                }
+                self.renew_search(self.search.variant());
+                self.sightline_vertex = None;
+                self.board_cache.clear();
+                self.heuristic_cache.clear();
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::DeleteObstacle(index) => {
+                self.context_menu = None;
+                self.board.remove_polygon(index);
+                self.renew_search(self.search.variant());
+                self.sightline_vertex = None;
+                self.board_cache.clear();
+                self.heuristic_cache.clear();
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ToggleObstacle(index) => {
+                self.context_menu = None;
+                let enabled = self.board.is_polygon_enabled(index);
+                self.board.set_polygon_enabled(index, !enabled);
+                self.renew_search(self.search.variant());
+                self.sightline_vertex = None;
+                self.board_cache.clear();
+                self.heuristic_cache.clear();
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::MeasureFrom(anchor) => {
+                self.context_menu = None;
+                self.measure_from = anchor;
+                Task::none()
+            }
+            Message::DiffHeuristics => {
+                let diff = self.search.diff_heuristics();
+                self.heuristic_diff = Some(
+                    diff.as_ref()
+                        .map_or_else(|| "No path to compare".to_string(), |diff| diff.to_string()),
+                );
+                self.diff_segments = diff.map(|diff| diff.segments);
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::SetWeight(weight) => {
+                self.is_playing = false;
+                self.weight = weight;
+                self.search.set_weight(weight);
+                self.renew_comparison();
+                self.heuristic_diff = None;
+                self.diff_segments = None;
+                self.trace = None;
+                self.is_tracing = false;
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::SetLineScale(line_scale) => {
+                self.line_scale = line_scale;
+                self.board_cache.clear();
+                self.search_cache.clear();
+                Task::none()
+            }
+            Message::ForkSearch => {
+                // Later heuristic or weight changes continue from this step
+                self.is_playing = false;
+                self.search = self.search.fork_from_current_step();
+                self.heuristic_diff = None;
+                self.diff_segments = None;
+                self.trace = None;
+                self.search_cache.clear();
+                Task::none()
+            }
             Message::Reset => {
                 self.search.reset();
                 self.search_cache.clear();
                 Task::none()
             }
+            Message::JumpToGoalDiscovery => {
+                if let Some(step) = self.search.goal_discovery_step() {
+                    self.is_playing = false;
+                    self.search.jump_to(step);
+                    self.search_cache.clear();
+                }
+                Task::none()
+            }
+            Message::PrevReopening => {
+                if let Some(step) = self.prev_reopening() {
+                    self.is_playing = false;
+                    self.search.jump_to(step);
+                    self.search_cache.clear();
+                }
+                Task::none()
+            }
+            Message::NextReopening => {
+                if let Some(step) = self.next_reopening() {
+                    self.is_playing = false;
+                    self.search.jump_to(step);
+                    self.search_cache.clear();
+                }
+                Task::none()
+            }
             Message::Finish => {
                 self.is_playing = false;
                 self.search.jump_to(self.search.total_steps());
@@ -246,6 +1447,22 @@ impl App {
         use keyboard::key;
 
         let mut batch = vec![keyboard::on_key_press(|key, modifiers| {
+            // Number keys toggle the overlays, in the order of their checkboxes
+            if let keyboard::Key::Character(c) = &key {
+                return match c.as_str() {
+                    "1" => Some(Message::ToggleSolution),
+                    "2" => Some(Message::ToggleSearchTree),
+                    "3" => Some(Message::ToggleCleanRender),
+                    "4" => Some(Message::ToggleFocusRender),
+                    "5" => Some(Message::ToggleFogOfWar),
+                    "6" => Some(Message::ToggleSizeByGScore),
+                    "g" => Some(Message::JumpToGoalDiscovery),
+                    "[" => Some(Message::PrevReopening),
+                    "]" => Some(Message::NextReopening),
+                    _ => None,
+                };
+            }
+
             let keyboard::Key::Named(key) = key else {
                 return None;
             };
@@ -254,29 +1471,128 @@ impl App {
                 (key::Named::F11, keyboard::Modifiers::SHIFT) => Some(Message::ToggleFullscreen),
                 (key::Named::Escape, _) => Some(Message::ChangeMode(window::Mode::Windowed)),
                 (key::Named::Space, _) => Some(Message::TogglePlay),
-                (key::Named::ArrowLeft, _) => Some(Message::Back),
-                (key::Named::ArrowRight, _) => Some(Message::Next),
+                (key::Named::ArrowLeft, _) => Some(Message::HoldStep(StepDirection::Back)),
+                (key::Named::ArrowRight, _) => Some(Message::HoldStep(StepDirection::Next)),
                 (key::Named::Home, _) => Some(Message::Reset),
                 (key::Named::End, _) => Some(Message::Finish),
                 _ => None,
             }
         })];
 
+        batch.push(keyboard::on_key_release(|key, _| match key {
+            keyboard::Key::Named(key::Named::ArrowLeft) => {
+                Some(Message::ReleaseStep(StepDirection::Back))
+            }
+            keyboard::Key::Named(key::Named::ArrowRight) => {
+                Some(Message::ReleaseStep(StepDirection::Next))
+            }
+            _ => None,
+        }));
+
+        if !self.held_steps.is_empty() {
+            batch.push(time::every(self.step_repeat).map(|_| Message::RepeatStep))
+        };
+
+        // Dropping a scenario file on the window loads it
+        batch.push(event::listen_with(|event, _, _| match event {
+            iced::Event::Window(window::Event::FileDropped(path)) => {
+                Some(Message::LoadScenario(path))
+            }
+            _ => None,
+        }));
+
         if self.is_playing {
-            batch.push(time::every(Duration::from_millis(200)).map(|_| Message::Tick))
+            batch.push(time::every(PLAYBACK_INTERVAL).map(|_| Message::Tick))
+        };
+
+        if self.is_tracing {
+            batch.push(time::every(Duration::from_millis(30)).map(|_| Message::TraceTick))
+        };
+
+        // Throttle replanning so we don't rebuild the search on every pixel
+        if self.follow_mouse {
+            batch.push(time::every(Duration::from_millis(50)).map(|_| Message::Replan))
+        };
+
+        // Recheck only once the cursor or goal moved since the last check
+        let reachability_stale = self
+            .hover_reachable
+            .is_none_or(|(goal, _)| goal != self.goal);
+        if self.show_reachability && self.hover.is_some() && reachability_stale {
+            batch.push(time::every(REACHABILITY_THROTTLE).map(|_| Message::CheckReachability))
         };
 
         iced::Subscription::batch(batch)
     }
 
+    /// The right-click menu, positioned at the click, or nothing if closed
+    fn context_menu<'a>(&self) -> Element<'a, Message> {
+        let Some(menu) = self.context_menu else {
+            return Space::new(0, 0).into();
+        };
+
+        let item = |label: &'static str, message| {
+            button(text(label).size(12))
+                .style(style::control)
+                .width(Length::Fixed(140.0))
+                .on_press(message)
+        };
+
+        let mut items = column![
+            item("Set start here", Message::SetStart(menu.point)),
+            item("Set goal here", Message::SetGoal(menu.point)),
+        ]
+        .spacing(2);
+        // Obstacle entries depend on whether the click landed inside one
+        items = match menu.polygon {
+            Some(index) => {
+                let toggle = if self.board.is_polygon_enabled(index) {
+                    "Disable obstacle"
+                } else {
+                    "Enable obstacle"
+                };
+                items
+                    .push(item("Delete obstacle", Message::DeleteObstacle(index)))
+                    .push(item(toggle, Message::ToggleObstacle(index)))
+            }
+            None => items.push(item("Add obstacle here", Message::AddObstacle(menu.point))),
+        };
+        items = items.push(item(
+            "Measure from here",
+            Message::MeasureFrom(Some(menu.point)),
+        ));
+        if self.measure_from.is_some() {
+            items = items.push(item("Stop measuring", Message::MeasureFrom(None)));
+        }
+        items = items
+            .push(item("Save scenario", Message::SaveScenario))
+            .push(item(
+                "Load scenario",
+                Message::LoadScenario(PathBuf::from(SCENARIO_FILE)),
+            ));
+        if self.search.visibility_graph().is_some() {
+            items = items.push(item("Export visibility graph", Message::ExportGraph));
+        }
+
+        container(items)
+            .padding(Padding {
+                top: menu.position.y,
+                left: menu.position.x,
+                ..Padding::ZERO
+            })
+            .into()
+    }
+
     fn controls<'a>(&self) -> Element<'a, Message> {
+        let is_running = self.search.status() == SearchStatus::Running;
+
         row![
             button(text("Reset").align_x(Center))
                 .style(style::reset)
                 .width(Length::Fixed(100.0))
                 .on_press(Message::Reset),
             button(
-                text(if !self.search.is_finished() {
+                text(if is_running {
                     match self.is_playing {
                         true => "Pause",
                         false => {
@@ -294,16 +1610,69 @@ impl App {
             )
             .style(style::control)
             .width(Length::Fixed(100.0))
-            .on_press_maybe(if !self.search.is_finished() {
+            .on_press_maybe(if is_running {
                 Some(Message::TogglePlay)
             } else {
                 None
             }),
+            button(text("Fork Here").align_x(Center))
+                .style(style::control)
+                .width(Length::Fixed(100.0))
+                .on_press_maybe((self.search.current_step() > 0).then_some(Message::ForkSearch)),
             horizontal_space(),
             row![
                 container(text("Heuristic:")).padding(5).align_y(Center),
-                pick_list(Heuristic::ALL, Some(self.heuristic), Message::PickHeuristic)
-            ],
+                pick_list(
+                    Heuristic::ALL
+                        .iter()
+                        .copied()
+                        .filter(|&heuristic| self.search.variant().supports(heuristic))
+                        .collect::<Vec<_>>(),
+                    Some(self.heuristic),
+                    Message::PickHeuristic
+                ),
+            ]
+            .push_maybe(match self.heuristic {
+                Heuristic::Blend(alpha) => Some(
+                    slider(0.0..=1.0, alpha, |alpha| {
+                        Message::PickHeuristic(Heuristic::Blend(alpha))
+                    })
+                    .step(0.05)
+                    .width(Length::Fixed(80.0)),
+                ),
+                _ => None,
+            })
+            .push(
+                button(text("Diff").align_x(Center))
+                    .style(style::control)
+                    .on_press(Message::DiffHeuristics),
+            )
+            .push(
+                container(text(self.heuristic_diff.clone().unwrap_or_default()).size(12))
+                    .padding(5)
+                    .align_y(Center),
+            )
+            .spacing(5),
+            row![
+                container(text(format!("w = {:.2}", self.weight)))
+                    .padding(5)
+                    .align_y(Center),
+                slider(1.0..=5.0, self.weight, Message::SetWeight)
+                    .step(0.1)
+                    .width(Length::Fixed(100.0)),
+                container(text(
+                    match self
+                        .optimal_cost
+                        .and_then(|cost| self.search.cost_ratio(cost))
+                    {
+                        Some(ratio) => format!("{ratio:.3}x optimal"),
+                        None => "No path".to_string(),
+                    }
+                ))
+                .padding(5)
+                .align_y(Center),
+            ]
+            .align_y(Center),
             horizontal_space(),
             container(
                 checkbox("Show Solution", self.show_solution)
@@ -311,6 +1680,109 @@ impl App {
             )
             .align_y(Center)
             .padding(5),
+            container(
+                checkbox("Show Search Tree", self.show_search_tree)
+                    .on_toggle(|_| { Message::ToggleSearchTree })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Clean Render", self.render_mode == RenderMode::Clean)
+                    .on_toggle(|_| { Message::ToggleCleanRender })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Focus Best Path", self.render_mode == RenderMode::Focus)
+                    .on_toggle(|_| { Message::ToggleFocusRender })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Fog of War", self.fog_of_war).on_toggle(|_| { Message::ToggleFogOfWar })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Size by Cost", self.size_by_g_score)
+                    .on_toggle(|_| { Message::ToggleSizeByGScore })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Expansion Order", self.expansion_gradient)
+                    .on_toggle(|_| { Message::ToggleExpansionGradient })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Precise Cost", self.precise_cost)
+                    .on_toggle(|_| { Message::TogglePreciseCost })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(checkbox("Show Axes", self.show_axes).on_toggle(|_| { Message::ToggleAxes }))
+                .align_y(Center)
+                .padding(5),
+            container(checkbox("Y Down", self.y_down).on_toggle(|_| { Message::ToggleYDown }))
+                .align_y(Center)
+                .padding(5),
+            container(
+                checkbox("Even-Odd Fill", self.fill_rule == fill::Rule::EvenOdd)
+                    .on_toggle(|_| { Message::ToggleFillRule })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Heuristic Field", self.show_heuristic_field)
+                    .on_toggle(|_| { Message::ToggleHeuristicField })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Straight Line", self.show_straight_line)
+                    .on_toggle(|_| { Message::ToggleStraightLine })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Sightlines", self.sightline_mode).on_toggle_maybe(
+                    (self.search.variant() == SearchVariant::VisibilityGraph)
+                        .then_some(|_| Message::ToggleSightlines)
+                )
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Reduced Graph", self.reduced_graph)
+                    .on_toggle(|_| { Message::ToggleReducedGraph })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Step by Edge", self.edge_granularity)
+                    .on_toggle(|_| { Message::ToggleEdgeGranularity })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Follow Mouse", self.follow_mouse)
+                    .on_toggle(|_| { Message::ToggleFollowMouse })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Compare Variants", self.comparison.is_some())
+                    .on_toggle(|_| { Message::ToggleComparison })
+            )
+            .align_y(Center)
+            .padding(5),
+            container(
+                checkbox("Reachability", self.show_reachability)
+                    .on_toggle(|_| { Message::ToggleReachability })
+            )
+            .align_y(Center)
+            .padding(5),
             horizontal_space(),
             button(text("Back").align_x(Center))
                 .style(style::control)
@@ -323,11 +1795,52 @@ impl App {
             button(text("Next").align_x(Center))
                 .style(style::control)
                 .width(Length::Fixed(100.0))
-                .on_press_maybe(if !self.search.is_finished() {
+                .on_press_maybe(if is_running {
                     Some(Message::Next)
                 } else {
                     None
                 }),
+            button(text("Prev Reopen").align_x(Center))
+                .style(style::control)
+                .width(Length::Fixed(100.0))
+                .on_press_maybe(self.prev_reopening().map(|_| Message::PrevReopening)),
+            button(text("Next Reopen").align_x(Center))
+                .style(style::control)
+                .width(Length::Fixed(100.0))
+                .on_press_maybe(self.next_reopening().map(|_| Message::NextReopening)),
+            button(text("Goal Found").align_x(Center))
+                .style(style::control)
+                .width(Length::Fixed(100.0))
+                .on_press_maybe(
+                    self.search
+                        .goal_discovery_step()
+                        .map(|_| Message::JumpToGoalDiscovery)
+                ),
+            row![button(text("Trace Path").align_x(Center))
+                .style(style::control)
+                .width(Length::Fixed(100.0))
+                .on_press_maybe(self.search.get_optimal_path().map(|_| Message::TracePath))]
+            .push_maybe(self.trace.map(|progress| {
+                slider(0.0..=1.0, progress, Message::SetTrace)
+                    .step(0.01)
+                    .width(Length::Fixed(100.0))
+            }))
+            .spacing(5)
+            .align_y(Center),
+            row![checkbox("Cost Frontier", self.cost_threshold.is_some())
+                .on_toggle(|_| Message::ToggleCostFrontier)]
+            .push_maybe(self.cost_threshold.map(|threshold| {
+                let max = self.max_g_score();
+                row![
+                    slider(0.0..=max, threshold.min(max), Message::SetCostThreshold)
+                        .width(Length::Fixed(100.0)),
+                    text(format!("≤ {:.0}", threshold.min(max))),
+                ]
+                .spacing(5)
+                .align_y(Center)
+            }))
+            .spacing(5)
+            .align_y(Center),
         ]
         .spacing(5)
         .padding(5)
@@ -337,23 +1850,8 @@ impl App {
 
     // Helper function to calculate transformation parameters
     fn get_transform_params(&self, bounds: Rectangle) -> (f32, iced::Vector) {
-        let (min_x, min_y, max_x, max_y) = self.board.bounds();
-
-        let board_width = (max_x - min_x) as f32;
-        let board_height = (max_y - min_y) as f32;
-
-        // Calculate the scaling to center board within frame and its new size
-        let scaling: f32 = 0.8 * (bounds.width / board_width).min(bounds.height / board_height);
-        let scaled_width = board_width * scaling;
-        let scaled_height = board_height * scaling;
-
-        // Calculate translation to center the scaled board within the frame
-        let translation = iced::Vector::new(
-            (bounds.width - scaled_width) / 2.0 - (min_x as f32 * scaling),
-            (bounds.height - scaled_height) / 2.0 + (max_y as f32 * scaling),
-        );
-
-        (scaling, translation)
+        self.board
+            .canvas_transform(bounds.width, bounds.height, self.y_down)
     }
 
     // Helper function to transform screen coordinates to board coordinates
@@ -362,9 +1860,9 @@ impl App {
 
         let board_x = (screen_pos.x - translation.x) / scaling;
 
-        // Since the board already flips y coordinates when drawing,
-        // we need to work with that convention
-        let board_y = -(screen_pos.y - translation.y) / scaling;
+        // Undo the y flip applied when drawing, if any
+        let canvas_y = (screen_pos.y - translation.y) / scaling;
+        let board_y = if self.y_down { canvas_y } else { -canvas_y };
 
         Point::new(board_x as i32, board_y as i32)
     }
@@ -379,23 +1877,116 @@ impl canvas::Program<Message> for App {
         renderer: &Renderer,
         _theme: &Theme,
         bounds: Rectangle,
-        _cursor: mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<Geometry> {
         let (scaling, translation) = self.get_transform_params(bounds);
 
         let board = self.board_cache.draw(renderer, bounds.size(), |frame| {
             frame.translate(translation);
             frame.scale(scaling);
-            self.board.draw(frame);
+            self.board.draw(
+                frame,
+                DrawOptions {
+                    line_scale: self.line_scale,
+                    show_axes: self.show_axes,
+                    fill_rule: self.fill_rule,
+                    y_down: self.y_down,
+                    ..DrawOptions::default()
+                },
+            );
+        });
+
+        let field = self.heuristic_cache.draw(renderer, bounds.size(), |frame| {
+            if self.show_heuristic_field {
+                frame.translate(translation);
+                frame.scale(scaling);
+                self.heuristic
+                    .draw_field(frame, &self.board, self.goal, self.y_down);
+            }
         });
 
         let search = self.search_cache.draw(renderer, bounds.size(), |frame| {
             frame.translate(translation);
             frame.scale(scaling);
-            self.search.draw(frame, self.show_solution);
+            let options = DrawOptions {
+                show_solution: self.show_solution,
+                show_search_tree: self.show_search_tree,
+                render_mode: self.render_mode,
+                fog_of_war: self.fog_of_war,
+                size_by_g_score: self.size_by_g_score,
+                expansion_gradient: self.expansion_gradient,
+                recent_edges: self
+                    .show_recent_edges
+                    .then_some(self.recent_edges_window as usize),
+                precise_cost: self.precise_cost,
+                show_axes: self.show_axes,
+                fill_rule: self.fill_rule,
+                corridor_width: self.show_corridor.then_some(self.corridor_width),
+                line_scale: self.line_scale,
+                y_down: self.y_down,
+            };
+            let frontier = self.cost_threshold.zip(self.search.history().last());
+            match frontier {
+                Some((threshold, last)) => {
+                    let state = last.within_cost(threshold.round() as i32);
+                    self.search.draw_state(frame, &state, options);
+                }
+                None => self.search.draw(frame, options),
+            }
+            self.agents.draw(frame, self.line_scale, self.y_down);
+            self.draw_sightlines(frame);
+            self.draw_trace(frame);
+            self.draw_straight_line(frame);
+            self.draw_comparison(frame);
+            self.draw_segment_diff(frame);
         });
 
-        vec![board, search]
+        // The ruler follows the cursor, so it's redrawn every frame
+        let mut ruler = Frame::new(renderer, bounds.size());
+        if let (true, Some(position)) = (self.show_reachability, cursor.position_in(bounds)) {
+            // Grey until the throttled check catches up with the cursor
+            let color = match self.hover_reachable {
+                Some((goal, reachable)) if goal == self.goal => {
+                    if reachable {
+                        Color::from_rgb8(50, 205, 50)
+                    } else {
+                        Color::from_rgb8(220, 20, 60)
+                    }
+                }
+                _ => Color::from_rgb8(160, 160, 160),
+            };
+            let indicator = Path::circle(position + iced::Vector::new(10.0, 10.0), 4.0);
+            ruler.fill(&indicator, color);
+        }
+        if let (Some(anchor), Some(position)) = (self.measure_from, cursor.position_in(bounds)) {
+            let target = self.screen_to_board_coords(position, bounds);
+            ruler.translate(translation);
+            ruler.scale(scaling);
+            ruler.stroke(
+                &Path::line(
+                    anchor.to_canvas_with(self.y_down),
+                    target.to_canvas_with(self.y_down),
+                ),
+                Stroke {
+                    line_dash: LineDash {
+                        segments: &[3.0, 3.0],
+                        offset: 0,
+                    },
+                    ..Default::default()
+                }
+                .with_color(Color::BLACK)
+                .with_width(self.line_scale),
+            );
+            ruler.fill_text(canvas::Text {
+                content: format!("{:.1}", Search::path_length_f64(&[anchor, target])),
+                position: target.to_canvas_with(self.y_down) + iced::Vector::new(3.0, -3.0),
+                color: Color::BLACK,
+                size: 5.0.into(),
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![board, field, search, ruler.into_geometry()]
     }
 
     fn update(
@@ -412,20 +2003,28 @@ impl canvas::Program<Message> for App {
         match event {
             Event::Mouse(mouse_event) => match mouse_event {
                 mouse::Event::ButtonPressed(button) => {
+                    let point = self.screen_to_board_coords(cursor_position, bounds);
                     let message = match button {
-                        mouse::Button::Left => {
-                            let new_start = self.screen_to_board_coords(cursor_position, bounds);
-                            Some(Message::SetStart(new_start))
+                        // A click outside an open menu only dismisses it
+                        mouse::Button::Left if self.context_menu.is_some() => {
+                            Some(Message::CloseContextMenu)
+                        }
+                        mouse::Button::Left if self.sightline_mode => {
+                            Some(Message::PickSightlineVertex(point))
                         }
+                        mouse::Button::Left => Some(Message::SetStart(point)),
                         mouse::Button::Right => {
-                            let new_goal = self.screen_to_board_coords(cursor_position, bounds);
-                            Some(Message::SetGoal(new_goal))
+                            Some(Message::OpenContextMenu(cursor_position, point))
                         }
                         _ => None,
                     };
 
                     (event::Status::Captured, message)
                 }
+                mouse::Event::CursorMoved { .. } if self.follow_mouse || self.show_reachability => {
+                    let point = self.screen_to_board_coords(cursor_position, bounds);
+                    (event::Status::Ignored, Some(Message::TrackCursor(point)))
+                }
                 _ => (event::Status::Ignored, None),
             },
             _ => (event::Status::Ignored, None),
@@ -1,15 +1,56 @@
-use iced::widget::canvas::{Fill, Frame, LineDash, Path, Stroke, Text};
+use iced::widget::canvas::{fill, Fill, Frame, LineCap, LineDash, LineJoin, Path, Stroke, Text};
 use iced::Color;
-use num_traits::{AsPrimitive, Signed};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
-use crate::{Board, Point};
+use crate::{Board, Coordinate, Edge, Point, Polygon};
 
-#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Heuristic {
     #[default]
     Euclidean,
     Manhattan,
+    /// `(1 - α) · euclidean + α · manhattan` for the given α, clamped to
+    /// [0, 1]. Admissible whenever both of its components are.
+    Blend(f32),
+    /// The ALT heuristic: lower bounds from the triangle inequality over
+    /// shortest path costs to `count` landmarks, picked with `strategy`.
+    ///
+    /// More landmarks tighten the estimate and reduce expansions, at the
+    /// price of one Dijkstra pass over the visibility graph each. Only the
+    /// visibility graph search precomputes landmarks; everywhere else, and at
+    /// least as a floor, this estimates like [`Heuristic::Euclidean`].
+    Landmark {
+        count: usize,
+        strategy: LandmarkStrategy,
+    },
+}
+
+/// How the landmarks of [`Heuristic::Landmark`] are picked among the
+/// obstacle vertices
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LandmarkStrategy {
+    /// Each landmark is the vertex farthest along the graph from those
+    /// already picked, which tends to spread them around the board's edges
+    #[default]
+    Farthest,
+    /// Landmarks picked uniformly at random, reproducibly from the seed
+    Random(u64),
+    /// The vertices closest to the corners of their bounding box, then
+    /// [`LandmarkStrategy::Farthest`] beyond four landmarks
+    Corners,
+}
+
+impl std::fmt::Display for LandmarkStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LandmarkStrategy::Farthest => write!(f, "farthest"),
+            LandmarkStrategy::Random(seed) => write!(f, "random #{seed}"),
+            LandmarkStrategy::Corners => write!(f, "corners"),
+        }
+    }
 }
 
 impl std::fmt::Display for Heuristic {
@@ -17,69 +58,588 @@ impl std::fmt::Display for Heuristic {
         match self {
             Heuristic::Euclidean => write!(f, "Euclidean"),
             Heuristic::Manhattan => write!(f, "Manhattan"),
+            Heuristic::Blend(alpha) => write!(f, "Blend ({alpha:.2})"),
+            Heuristic::Landmark { count, strategy } => {
+                write!(f, "Landmarks ({count}, {strategy})")
+            }
         }
     }
 }
 
 impl Heuristic {
-    pub const ALL: &'static [Heuristic] = &[Heuristic::Euclidean, Heuristic::Manhattan];
+    pub const ALL: &'static [Heuristic] = &[
+        Heuristic::Euclidean,
+        Heuristic::Manhattan,
+        Heuristic::Blend(0.5),
+        Heuristic::Landmark {
+            count: 4,
+            strategy: LandmarkStrategy::Farthest,
+        },
+    ];
 
-    pub fn distance<T>(self, p1: &Point<T>, p2: &Point<T>) -> T
-    where
-        T: Copy
-            + Default
-            + Signed
-            + std::ops::Sub<Output = T>
-            + std::ops::Add<Output = T>
-            + std::ops::Mul<Output = T>
-            + AsPrimitive<f64>,
-        f64: AsPrimitive<T>,
-    {
+    /// The ALT heuristic with `count` landmarks picked with `strategy`
+    pub fn landmarks(count: usize, strategy: LandmarkStrategy) -> Self {
+        Heuristic::Landmark { count, strategy }
+    }
+
+    pub fn distance<T: Coordinate>(self, p1: &Point<T>, p2: &Point<T>) -> T {
         match self {
             Heuristic::Manhattan => {
                 let dx = num_traits::abs(p2.x - p1.x);
                 let dy = num_traits::abs(p2.y - p1.y);
                 dx + dy
             }
-            Heuristic::Euclidean => {
+            Heuristic::Euclidean | Heuristic::Landmark { .. } => {
                 let dx = p2.x - p1.x;
                 let dy = p2.y - p1.y;
                 let squared = dx * dx + dy * dy;
-                let float_result = squared.as_();
-                (float_result.sqrt()).as_()
+                let float_result: f64 = squared.as_();
+                T::from_f64(float_result.sqrt())
+            }
+            Heuristic::Blend(alpha) => {
+                let alpha = f64::from(alpha.clamp(0.0, 1.0));
+                let dx: f64 = (p2.x - p1.x).as_();
+                let dy: f64 = (p2.y - p1.y).as_();
+                let euclidean = dx.hypot(dy);
+                let manhattan = dx.abs() + dy.abs();
+                T::from_f64((1.0 - alpha) * euclidean + alpha * manhattan)
+            }
+        }
+    }
+
+    /// The [`Heuristic::distance`] inflated by `weight`, as used by weighted
+    /// A*. A weight of 1.0 returns the plain distance.
+    pub fn weighted_distance<T: Coordinate>(self, p1: &Point<T>, p2: &Point<T>, weight: f32) -> T {
+        let distance = self.distance(p1, p2);
+        if weight == 1.0 {
+            return distance;
+        }
+        let distance: f64 = distance.as_();
+        T::from_f64(distance * weight as f64)
+    }
+
+    /// Tints the board's [`Board::bounds`] by the distance to `goal`, sampled
+    /// at the center of each cell of a coarse grid, from warm near the goal
+    /// to cool far from it. See [`Point::to_canvas_with`] for `y_down`.
+    pub fn draw_field<T: Coordinate>(
+        self,
+        frame: &mut Frame,
+        board: &Board<T>,
+        goal: Point<T>,
+        y_down: bool,
+    ) {
+        let (min_x, min_y, max_x, max_y) = board.bounds();
+        let (min_x, min_y, max_x, max_y): (f32, f32, f32, f32) =
+            (min_x.as_(), min_y.as_(), max_x.as_(), max_y.as_());
+
+        let columns = ((max_x - min_x) / HEURISTIC_FIELD_CELL).ceil() as usize;
+        let rows = ((max_y - min_y) / HEURISTIC_FIELD_CELL).ceil() as usize;
+        let cells: Vec<_> = (0..columns)
+            .flat_map(|c| (0..rows).map(move |r| (c, r)))
+            .map(|(c, r)| {
+                let x = min_x + c as f32 * HEURISTIC_FIELD_CELL;
+                let y = min_y + r as f32 * HEURISTIC_FIELD_CELL;
+                let center = Point::new(
+                    T::from_f64(f64::from(x + HEURISTIC_FIELD_CELL / 2.0)),
+                    T::from_f64(f64::from(y + HEURISTIC_FIELD_CELL / 2.0)),
+                );
+                let value: f32 = self.distance(&center, &goal).as_();
+                (x, y, value)
+            })
+            .collect();
+
+        let max_value = cells.iter().map(|&(_, _, v)| v).fold(0.0, f32::max);
+        if max_value <= 0.0 {
+            return;
+        }
+
+        let (near, far) = ((255.0, 214.0, 112.0), (84.0, 126.0, 220.0));
+        for (x, y, value) in cells {
+            let t = value / max_value;
+            let lerp = |a: f32, b: f32| (a + (b - a) * t) as u8;
+            let color = Color::from_rgba8(
+                lerp(near.0, far.0),
+                lerp(near.1, far.1),
+                lerp(near.2, far.2),
+                0.35,
+            );
+            // Canvas y grows downward, so unless the board does too, the
+            // cell's top edge is at -(y + size)
+            let top = if y_down {
+                y
+            } else {
+                -(y + HEURISTIC_FIELD_CELL)
+            };
+            frame.fill_rectangle(
+                iced::Point::new(x, top),
+                iced::Size::new(HEURISTIC_FIELD_CELL, HEURISTIC_FIELD_CELL),
+                color,
+            );
+        }
+    }
+}
+
+/// Secondary ordering applied to open nodes whose f-scores are tied
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// Expand tied nodes in the order they were pushed
+    #[default]
+    FifoStable,
+    /// Prefer the node with the higher g-score, i.e. deeper into the search
+    HigherG,
+    /// Prefer the node with the lower h-score, i.e. closer to the goal
+    LowerH,
+}
+
+/// Data structure holding the open nodes of a search, which changes how fast
+/// the search runs but never which nodes it expands
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenSetKind {
+    /// Binary heap with O(log n) pushes and pops, leaving superseded nodes
+    /// in place to skip when popped
+    #[default]
+    BinaryHeap,
+    /// Vector kept sorted on every push, with O(1) pops and superseded
+    /// nodes removed right away
+    SortedVec,
+}
+
+/// Directions in which a path may travel between consecutive vertices
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementModel {
+    /// Move in a straight line between any two mutually visible points
+    #[default]
+    AnyAngle,
+    /// Move only horizontally or vertically. Pairs naturally with
+    /// [`Heuristic::Manhattan`].
+    Orthogonal,
+}
+
+impl MovementModel {
+    /// Returns true if a move from `from` to `to` is allowed under this model
+    pub fn allows<T: Coordinate>(self, from: &Point<T>, to: &Point<T>) -> bool {
+        match self {
+            MovementModel::AnyAngle => true,
+            MovementModel::Orthogonal => from.x == to.x || from.y == to.y,
+        }
+    }
+
+    /// Returns the points a path may turn at or end on, in a deterministic
+    /// order. Any-angle paths only turn at obstacle vertices, while orthogonal
+    /// paths turn anywhere on the grid of x and y coordinates taken from the
+    /// obstacle vertices, start and goal, so long as it lies outside every
+    /// obstacle.
+    pub fn waypoints<T: Coordinate>(
+        self,
+        board: &Board<T>,
+        start: Point<T>,
+        goal: Point<T>,
+    ) -> Vec<Point<T>> {
+        match self {
+            MovementModel::AnyAngle => {
+                let mut vertices = board.vertices_ordered();
+                if !vertices.contains(&goal) {
+                    vertices.push(goal);
+                }
+                vertices
+            }
+            MovementModel::Orthogonal => {
+                let vertices = board.enabled_polygons().flat_map(|p| p.vertices().copied());
+                let by = |a: &T, b: &T| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+                let points: Vec<_> = vertices.chain([start, goal]).collect();
+
+                let mut xs: Vec<T> = points.iter().map(|p| p.x).collect();
+                let mut ys: Vec<T> = points.iter().map(|p| p.y).collect();
+                xs.sort_by(by);
+                xs.dedup();
+                ys.sort_by(by);
+                ys.dedup();
+
+                xs.iter()
+                    .flat_map(|&x| ys.iter().map(move |&y| Point::new(x, y)))
+                    .filter(|p| *p != start && !board.contains_point(p))
+                    .collect()
             }
         }
     }
 }
 
+/// Outcome of a search at the current step of its playback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStatus {
+    /// Playback has not yet reached the final step
+    Running,
+    /// The search reached the goal
+    Found,
+    /// The search exhausted every reachable vertex without reaching the goal
+    NoPath,
+    /// The search stopped early without reaching the goal, e.g. a windowed
+    /// search that ran out of vertices while leaving moves beyond its radius
+    /// unexplored, so a path may still exist
+    Incomplete,
+}
+
+/// Distance from an explored point within which fog of war reveals obstacles
+const FOG_REVEAL_RADIUS: f64 = 25.0;
+
+/// Side length of the grid cells sampled by [`Heuristic::draw_field`]
+const HEURISTIC_FIELD_CELL: f32 = 10.0;
+
+/// Smallest and largest vertex radii when sizing vertices by g-score
+const G_SCORE_RADIUS_RANGE: (f32, f32) = (0.5, 3.0);
+
+/// Colors of the first and last expanded vertices when shading by expansion
+/// order
+const EXPANSION_GRADIENT: (Color, Color) = (
+    Color::from_rgb(0.47, 0.08, 0.08),
+    Color::from_rgb(1.0, 0.78, 0.78),
+);
+
+/// Opacity multiplier for search progress off the best path in
+/// [`RenderMode::Focus`]
+const FOCUS_DIM_ALPHA: f32 = 0.2;
+
+/// How much of the search [`Pathfinder::draw`] renders
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Draw the board along with every piece of search progress
+    #[default]
+    Full,
+    /// Draw only the board, start, goal and optimal path, e.g. for screenshots
+    Clean,
+    /// Draw the search progress faded, highlighting only the nodes and edges
+    /// of the best path so far
+    Focus,
+}
+
+/// Toggles for the optional overlays drawn by [`Pathfinder::draw`]
+#[derive(Debug, Clone, Copy)]
+pub struct DrawOptions {
+    /// Draw the optimal path as a dashed line
+    pub show_solution: bool,
+    /// Draw each vertex's `came_from` parent pointer, revealing the search tree
+    pub show_search_tree: bool,
+    pub render_mode: RenderMode,
+    /// Hide obstacles until the search explores a point near them. Showing
+    /// the solution reveals the whole board.
+    pub fog_of_war: bool,
+    /// Size open and closed vertices by their g-score instead of uniformly
+    pub size_by_g_score: bool,
+    /// Shade closed vertices by when they were expanded, from dark for the
+    /// first to light for the last
+    pub expansion_gradient: bool,
+    /// Draw only the edges considered in this many of the latest expansions
+    /// instead of every edge considered so far
+    pub recent_edges: Option<usize>,
+    /// Label costs with their exact length to one decimal place instead of
+    /// the integer score
+    pub precise_cost: bool,
+    /// Draw the board's tick marks and coordinate labels
+    pub show_axes: bool,
+    /// Fill rule for obstacles, which only matters for polygons with holes
+    pub fill_rule: fill::Rule,
+    /// Draw the optimal path as a band this wide, e.g. a robot's diameter,
+    /// highlighting segments that pass closer than half of it to an obstacle
+    pub corridor_width: Option<f32>,
+    /// Factor applied to every stroke width and circle radius
+    pub line_scale: f32,
+    /// Draw y growing downward, for boards in screen coordinates, instead of
+    /// flipping it to match mathematical coordinates
+    pub y_down: bool,
+}
+
+impl Default for DrawOptions {
+    fn default() -> Self {
+        Self {
+            show_solution: false,
+            show_search_tree: false,
+            render_mode: RenderMode::default(),
+            fog_of_war: false,
+            size_by_g_score: false,
+            expansion_gradient: false,
+            recent_edges: None,
+            precise_cost: false,
+            show_axes: true,
+            fill_rule: fill::Rule::NonZero,
+            corridor_width: None,
+            line_scale: 1.0,
+            y_down: false,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
-pub struct SearchState {
-    pub open: HashSet<Point>,
-    pub closed: HashSet<Point>,
-    pub current_paths: HashMap<Point, Vec<Point>>,
-    pub best_path: Option<Vec<Point>>,
-    pub considered_edges: HashSet<(Point, Point)>,
-    pub next_vertex: Option<Point>,
-    pub g_scores: HashMap<Point, i32>,
-    pub came_from: HashMap<Point, Point>,
+pub struct SearchState<T = i32> {
+    pub open: HashSet<Point<T>>,
+    pub closed: HashSet<Point<T>>,
+    pub current_paths: HashMap<Point<T>, Vec<Point<T>>>,
+    pub best_path: Option<Vec<Point<T>>>,
+    pub considered_edges: HashSet<(Point<T>, Point<T>)>,
+    /// How many vertices had been expanded when each considered edge was
+    /// last relaxed
+    pub edge_steps: HashMap<(Point<T>, Point<T>), usize>,
+    pub next_vertex: Option<Point<T>>,
+    pub g_scores: HashMap<Point<T>, T>,
+    pub came_from: HashMap<Point<T>, Point<T>>,
+    /// When each closed vertex was first expanded, counting from 0 at the
+    /// start
+    pub expansion_order: HashMap<Point<T>, usize>,
+    /// The edge relaxed in this frame, when stepping edge by edge
+    pub active_edge: Option<(Point<T>, Point<T>)>,
+}
+
+impl<T: Coordinate> SearchState<T> {
+    /// Creates the initial [`SearchState`] with only the start point open
+    pub fn new(start: Point<T>) -> Self {
+        Self {
+            open: HashSet::from([start]),
+            closed: HashSet::new(),
+            current_paths: HashMap::from([(start, vec![start])]),
+            best_path: None,
+            considered_edges: HashSet::new(),
+            edge_steps: HashMap::new(),
+            next_vertex: Some(start),
+            g_scores: HashMap::from([(start, T::zero())]),
+            came_from: HashMap::new(),
+            expansion_order: HashMap::new(),
+            active_edge: None,
+        }
+    }
+
+    /// Closes `vertex`, numbering it in [`SearchState::expansion_order`]
+    /// unless it was already expanded once
+    pub fn close(&mut self, vertex: Point<T>) {
+        self.closed.insert(vertex);
+        let order = self.expansion_order.len();
+        self.expansion_order.entry(vertex).or_insert(order);
+    }
+
+    /// Records that the search relaxed the edge from `from` to `to`, stamping
+    /// it with the current expansion for [`SearchState::recent_edges`]
+    pub fn consider(&mut self, from: Point<T>, to: Point<T>) {
+        self.considered_edges.insert((from, to));
+        self.edge_steps
+            .insert((from, to), self.expansion_order.len());
+    }
+
+    /// Returns the open vertices ordered by coordinate
+    pub fn open_sorted(&self) -> Vec<Point<T>> {
+        let mut open: Vec<_> = self.open.iter().copied().collect();
+        open.sort_by(compare_points);
+        open
+    }
+
+    /// Returns the closed vertices ordered by coordinate
+    pub fn closed_sorted(&self) -> Vec<Point<T>> {
+        let mut closed: Vec<_> = self.closed.iter().copied().collect();
+        closed.sort_by(compare_points);
+        closed
+    }
+
+    /// Returns the part of this state within `threshold` of the start: the
+    /// open and closed vertices whose g-score is at most `threshold`, with
+    /// the paths and edges between them. Applied to a finished search, this
+    /// is the cost frontier at `threshold` regardless of expansion order.
+    pub fn within_cost(&self, threshold: T) -> Self {
+        let within = |v: &Point<T>| self.g_scores.get(v).is_some_and(|&g| g <= threshold);
+
+        Self {
+            open: self.open.iter().copied().filter(within).collect(),
+            closed: self.closed.iter().copied().filter(within).collect(),
+            current_paths: self
+                .current_paths
+                .iter()
+                .filter(|(v, _)| within(v))
+                .map(|(&v, path)| (v, path.clone()))
+                .collect(),
+            best_path: None,
+            considered_edges: self
+                .considered_edges
+                .iter()
+                .copied()
+                .filter(|(from, to)| within(from) && within(to))
+                .collect(),
+            edge_steps: self
+                .edge_steps
+                .iter()
+                .filter(|((from, to), _)| within(from) && within(to))
+                .map(|(&edge, &step)| (edge, step))
+                .collect(),
+            next_vertex: None,
+            g_scores: self
+                .g_scores
+                .iter()
+                .filter(|(v, _)| within(v))
+                .map(|(&v, &g)| (v, g))
+                .collect(),
+            came_from: self
+                .came_from
+                .iter()
+                .filter(|(child, _)| within(child))
+                .map(|(&child, &parent)| (child, parent))
+                .collect(),
+            expansion_order: self
+                .expansion_order
+                .iter()
+                .filter(|(v, _)| within(v))
+                .map(|(&v, &order)| (v, order))
+                .collect(),
+            active_edge: None,
+        }
+    }
+
+    /// Returns the considered edges ordered by their start, then end
+    pub fn considered_edges_sorted(&self) -> Vec<(Point<T>, Point<T>)> {
+        let mut edges: Vec<_> = self.considered_edges.iter().copied().collect();
+        edges.sort_by(|(a1, a2), (b1, b2)| compare_points(a1, b1).then(compare_points(a2, b2)));
+        edges
+    }
+
+    /// Returns the considered edges relaxed during the latest `window`
+    /// expansions, ordered like [`SearchState::considered_edges_sorted`]
+    pub fn recent_edges(&self, window: usize) -> Vec<(Point<T>, Point<T>)> {
+        let expansions = self.expansion_order.len();
+        let mut edges = self.considered_edges_sorted();
+        edges.retain(|edge| {
+            self.edge_steps
+                .get(edge)
+                .is_some_and(|&step| step + window > expansions)
+        });
+        edges
+    }
 }
 
-/// Common interface for pathfinding algorithms
-pub trait Pathfinder {
+/// Returns the first frame in `history` where `goal` has entered the open set
+pub(crate) fn goal_discovery_step<T: Coordinate>(
+    history: &[SearchState<T>],
+    goal: &Point<T>,
+) -> Option<usize> {
+    history.iter().position(|state| state.open.contains(goal))
+}
+
+/// Returns the frames in `history` at which a closed vertex's g-score
+/// dropped, i.e. where the search found a cheaper path to it and reopened it
+pub(crate) fn reopening_steps<T: Coordinate>(history: &[SearchState<T>]) -> Vec<usize> {
+    history
+        .windows(2)
+        .enumerate()
+        .filter(|(_, frames)| {
+            let (before, after) = (&frames[0], &frames[1]);
+            before.closed.iter().any(|v| {
+                matches!(
+                    (before.g_scores.get(v), after.g_scores.get(v)),
+                    (Some(old), Some(new)) if new < old
+                )
+            })
+        })
+        .map(|(i, _)| i + 1)
+        .collect()
+}
+
+/// Returns the Hausdorff distance between two non-empty paths: the farthest
+/// any vertex of one path lies from the other path as a polyline. Only
+/// vertices are measured, so this is a lower bound on the true distance: the
+/// farthest point of one path can lie partway along a segment, e.g. where it
+/// passes a bend of the other path on the outside.
+pub(crate) fn hausdorff_distance<T: Coordinate>(a: &[Point<T>], b: &[Point<T>]) -> f64 {
+    // Distance to a polyline, treating a single point as a degenerate edge
+    let to_path = |p: &Point<T>, path: &[Point<T>]| {
+        let edges = path
+            .windows(2)
+            .map(|w| Edge::new(w[0], w[1]))
+            .chain((path.len() == 1).then(|| Edge::new(path[0], path[0])));
+        edges
+            .map(|edge| edge.distance_to_point(p))
+            .fold(f64::INFINITY, f64::min)
+    };
+    let directed = |from: &[Point<T>], to: &[Point<T>]| {
+        from.iter().map(|p| to_path(p, to)).fold(0.0, f64::max)
+    };
+
+    directed(a, b).max(directed(b, a))
+}
+
+/// The segments of two paths split into those both share and those only one
+/// has, see [`path_segment_diff`]
+pub type SegmentDiff<T = i32> = (Vec<Edge<T>>, Vec<Edge<T>>, Vec<Edge<T>>);
+
+/// Compares the segments of two paths, ignoring direction, returning those
+/// both share and those only in `a` or only in `b`, each in path order
+pub fn path_segment_diff<T: Coordinate>(a: &[Point<T>], b: &[Point<T>]) -> SegmentDiff<T> {
+    let segments = |path: &[Point<T>]| -> Vec<Edge<T>> {
+        path.windows(2).map(|w| Edge::new(w[0], w[1])).collect()
+    };
+    let undirected = |segments: &[Edge<T>]| -> HashSet<Edge<T>> {
+        segments
+            .iter()
+            .flat_map(|edge| [*edge, Edge::new(edge.end, edge.start)])
+            .collect()
+    };
+    let (a, b) = (segments(a), segments(b));
+    let (in_a, in_b) = (undirected(&a), undirected(&b));
+
+    let (shared, only_a) = a.into_iter().partition(|edge| in_b.contains(edge));
+    let only_b = b.into_iter().filter(|edge| !in_a.contains(edge)).collect();
+    (shared, only_a, only_b)
+}
+
+/// Rounds each interior corner of a path by cutting it with a segment between
+/// the points a quarter of the way along its two edges, as in one pass of
+/// Chaikin's algorithm. Corners whose cut would cross an obstacle are kept,
+/// so a path hugging obstacle vertices only changes where it turns in open
+/// space.
+pub(crate) fn smooth_path<T: Coordinate>(board: &Board<T>, path: &[Point<T>]) -> Vec<Point<T>> {
+    let toward = |from: Point<T>, to: Point<T>| {
+        let step = |a: T, b: T| {
+            let (a, b): (f64, f64) = (a.as_(), b.as_());
+            T::round_f64(a + (b - a) / 4.0)
+        };
+        Point::new(step(from.x, to.x), step(from.y, to.y))
+    };
+
+    let mut smoothed: Vec<_> = path.first().copied().into_iter().collect();
+    for window in path.windows(3) {
+        let (previous, corner, next) = (window[0], window[1], window[2]);
+        let (entry, exit) = (toward(corner, previous), toward(corner, next));
+
+        if entry != exit && board.path_is_valid(&[entry, exit]) {
+            smoothed.extend([entry, exit]);
+        } else {
+            smoothed.push(corner);
+        }
+    }
+    smoothed.extend(path.last().filter(|_| path.len() > 1));
+    smoothed
+}
+
+/// Orders [`Point`]s by x, then y, so `HashSet` contents render deterministically
+fn compare_points<T: Coordinate>(a: &Point<T>, b: &Point<T>) -> Ordering {
+    let by = |a: T, b: T| a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+    by(a.x, b.x).then(by(a.y, b.y))
+}
+
+/// Common interface for pathfinding algorithms, generic over the
+/// [`Coordinate`] type of the board
+pub trait Pathfinder<T: Coordinate = i32> {
     /// Required methods that implementations must provide
-    fn get_board(&self) -> &Board;
-    fn get_state(&self) -> &SearchState;
-    fn get_start(&self) -> Point;
-    fn get_goal(&self) -> Point;
+    fn get_board(&self) -> &Board<T>;
+    fn get_state(&self) -> &SearchState<T>;
+    fn get_start(&self) -> Point<T>;
+    fn get_goal(&self) -> Point<T>;
     fn get_heuristic(&self) -> Heuristic;
 
     /// Initialize a new pathfinder
-    fn new(board: Board, start: Point, goal: Point, heuristic: Heuristic) -> Self
+    fn new(board: Board<T>, start: Point<T>, goal: Point<T>, heuristic: Heuristic) -> Self
     where
         Self: Sized;
 
     /// Get optimal path and cost if found
-    fn get_optimal_path(&self) -> Option<&(Vec<Point>, i32)>;
+    fn get_optimal_path(&self) -> Option<&(Vec<Point<T>>, T)>;
+
+    /// Consume the pathfinder, keeping only its optimal path and cost
+    fn into_optimal_path(self) -> Option<(Vec<Point<T>>, T)>
+    where
+        Self: Sized;
 
     /// Total steps in visualization
     fn total_steps(&self) -> usize;
@@ -91,16 +651,32 @@ pub trait Pathfinder {
     fn step_forward(&mut self) -> bool;
     fn step_back(&mut self) -> bool;
     fn jump_to(&mut self, step: usize) -> bool;
+    /// The first step at which the goal is in the open set, if it ever is
+    fn goal_discovery_step(&self) -> Option<usize>;
+    /// Steps at which a closed vertex was reopened, in increasing order
+    fn reopening_steps(&self) -> &[usize];
+    /// Wall-clock time spent generating and relaxing successors after each
+    /// history frame, indexed by step. Every frame but the last has one.
+    fn step_timings(&self) -> &[Duration];
     fn reset(&mut self);
     fn change_heuristic(&mut self, heuristic: Heuristic);
 
-    /// Default implementation for checking if finished
+    /// Status of the search at the current step
+    fn status(&self) -> SearchStatus;
+
+    /// Default implementation for checking if finished. This is true whether
+    /// the search reached the goal or gave up, see [`Pathfinder::found_path`]
     fn is_finished(&self) -> bool {
         self.current_step() >= self.total_steps()
     }
 
+    /// Whether playback has finished with the goal reached
+    fn found_path(&self) -> bool {
+        self.status() == SearchStatus::Found
+    }
+
     /// Default implementation for path reconstruction
-    fn reconstruct_path(&self, vertex: &Point) -> Vec<Point> {
+    fn reconstruct_path(&self, vertex: &Point<T>) -> Vec<Point<T>> {
         let mut path = vec![*vertex];
         let mut current = *vertex;
 
@@ -113,116 +689,322 @@ pub trait Pathfinder {
         path
     }
 
-    /// Default implementation for best path score
-    fn best_path_score(&self) -> Option<i32> {
+    /// Default implementation for best path score, priced with
+    /// [`Pathfinder::edge_cost`] like the search itself
+    fn best_path_score(&self) -> Option<T> {
         self.get_state().best_path.as_ref().map(|path| {
             path.windows(2)
-                .map(|window| Self::distance(&window[0], &window[1]))
+                .map(|window| self.edge_cost(&window[0], &window[1]))
                 .sum()
         })
     }
 
+    /// Owned copy of the optimal path and cost, for callers that can't keep
+    /// the pathfinder borrowed
+    fn optimal_path_cloned(&self) -> Option<(Vec<Point<T>>, T)> {
+        self.get_optimal_path().cloned()
+    }
+
     /// Default implementation for optimal path score
-    fn optimal_path_score(&self) -> Option<i32> {
+    fn optimal_path_score(&self) -> Option<T> {
         self.get_optimal_path().map(|(_, score)| *score)
     }
 
+    /// Ratio of the optimal path's length to the straight-line distance from
+    /// start to goal, ignoring obstacles. This is 1.0 when the endpoints see
+    /// each other or coincide, and `None` if no path was found.
+    fn detour_factor(&self) -> Option<f64> {
+        let (path, _) = self.get_optimal_path()?;
+        let straight = Self::path_length_f64(&[self.get_start(), self.get_goal()]);
+        if straight == 0.0 {
+            return Some(1.0);
+        }
+        Some(Self::path_length_f64(path) / straight)
+    }
+
+    /// Area of the convex hull of every open and closed vertex at the current
+    /// step, a rough measure of how much of the board the search has touched.
+    /// Fewer than three distinct vertices enclose no area.
+    fn explored_area(&self) -> f64 {
+        let state = self.get_state();
+        let explored: HashSet<_> = state.open.union(&state.closed).copied().collect();
+        if explored.len() < 3 {
+            return 0.0;
+        }
+
+        let explored: Vec<_> = explored.into_iter().collect();
+        Polygon::convex_hull(&explored).area()
+    }
+
+    /// Returns the minimum and maximum corners of the box enclosing every open
+    /// and closed vertex at the current step, e.g. to frame the search
+    /// frontier, or `None` if both sets are empty
+    fn frontier_bounds(&self) -> Option<(Point<T>, Point<T>)> {
+        let state = self.get_state();
+        let mut frontier = state.open.iter().chain(&state.closed);
+        let first = *frontier.next()?;
+
+        let min = |a: T, b: T| if b < a { b } else { a };
+        let max = |a: T, b: T| if b > a { b } else { a };
+
+        Some(frontier.fold((first, first), |(lo, hi), v| {
+            (
+                Point::new(min(lo.x, v.x), min(lo.y, v.y)),
+                Point::new(max(hi.x, v.x), max(hi.y, v.y)),
+            )
+        }))
+    }
+
     /// Default implementation for Euclidean distance
-    fn distance(p1: &Point, p2: &Point) -> i32 {
-        let dx = p2.x - p1.x;
-        let dy = p2.y - p1.y;
-        ((dx * dx + dy * dy) as f64).sqrt() as i32
+    fn distance(p1: &Point<T>, p2: &Point<T>) -> T {
+        Heuristic::Euclidean.distance(p1, p2)
+    }
+
+    /// Exact Euclidean length of a path, without the per-edge rounding of
+    /// [`Pathfinder::distance`]
+    fn path_length_f64(path: &[Point<T>]) -> f64 {
+        path.windows(2)
+            .map(|window| {
+                let dx: f64 = (window[1].x - window[0].x).as_();
+                let dy: f64 = (window[1].y - window[0].y).as_();
+                dx.hypot(dy)
+            })
+            .sum()
+    }
+
+    /// The first `length` units of a path, ending partway along a segment
+    /// where needed, e.g. to animate the path being drawn from start to goal
+    fn path_prefix(path: &[Point<T>], length: f64) -> Vec<Point<T>> {
+        let mut prefix: Vec<_> = path.first().copied().into_iter().collect();
+        let mut remaining = length;
+
+        for window in path.windows(2) {
+            let segment = Self::path_length_f64(window);
+            if remaining >= segment {
+                prefix.push(window[1]);
+                remaining -= segment;
+                continue;
+            }
+
+            if remaining > 0.0 {
+                let t = remaining / segment;
+                let lerp = |a: T, b: T| {
+                    let (a, b): (f64, f64) = (a.as_(), b.as_());
+                    T::round_f64(a + (b - a) * t)
+                };
+                prefix.push(Point::new(
+                    lerp(window[0].x, window[1].x),
+                    lerp(window[0].y, window[1].y),
+                ));
+            }
+            break;
+        }
+
+        prefix
+    }
+
+    /// Cost of moving along the edge between two points. Defaults to the
+    /// Euclidean [`Pathfinder::distance`]; override to plug in another cost model
+    fn edge_cost(&self, from: &Point<T>, to: &Point<T>) -> T {
+        Self::distance(from, to)
     }
 
     /// Default implementation for drawing current state
-    fn draw(&self, frame: &mut Frame, show_solution: bool) {
-        // First draw the board
-        self.get_board().draw(frame);
-
-        // Draw historical considered edges
-        let historical_stroke = Stroke::default()
-            .with_color(Color::from_rgba8(128, 128, 128, 0.3))
-            .with_width(1.0);
-
-        for (from, to) in &self.get_state().considered_edges {
-            let path = Path::line(
-                (from.x as f32, -from.y as f32).into(),
-                (to.x as f32, -to.y as f32).into(),
-            );
-            frame.stroke(&path, historical_stroke);
+    fn draw(&self, frame: &mut Frame, options: DrawOptions) {
+        self.draw_state(frame, self.get_state(), options);
+    }
+
+    /// Draws the board, endpoints and solution with the open and closed sets,
+    /// paths and edges of `state` in place of the current step's
+    fn draw_state(&self, frame: &mut Frame, state: &SearchState<T>, options: DrawOptions) {
+        // First draw the board, revealing only what the search has explored
+        // so far when fog of war is on
+        if options.fog_of_war && !options.show_solution {
+            let explored: Vec<_> = state.open.iter().chain(&state.closed).copied().collect();
+            self.get_board()
+                .draw_revealed(frame, options, &explored, FOG_REVEAL_RADIUS);
+        } else {
+            self.get_board().draw(frame, options);
         }
 
-        // Draw current active paths
-        let current_stroke = Stroke::default()
-            .with_color(Color::from_rgba8(0, 100, 255, 0.5))
-            .with_width(2.0);
+        let full = options.render_mode != RenderMode::Clean;
+        let focus = options.render_mode == RenderMode::Focus;
+        let dim = |color: Color| {
+            if focus {
+                Color {
+                    a: color.a * FOCUS_DIM_ALPHA,
+                    ..color
+                }
+            } else {
+                color
+            }
+        };
+        // Best path so far, to highlight its nodes in focus mode
+        let mut focus_path = None;
+
+        if full {
+            // Draw historical considered edges
+            let historical_stroke = Stroke::default()
+                .with_color(dim(Color::from_rgba8(128, 128, 128, 0.3)))
+                .with_width(options.line_scale);
 
-        // Find path closest to goal
-        let mut best_current_path = None;
-        let mut best_distance_to_goal = i32::MAX;
+            let considered_edges = match options.recent_edges {
+                Some(window) => state.recent_edges(window),
+                None => state.considered_edges_sorted(),
+            };
+            for (from, to) in considered_edges {
+                let path = Path::line(
+                    from.to_canvas_with(options.y_down),
+                    to.to_canvas_with(options.y_down),
+                );
+                frame.stroke(&path, historical_stroke);
+            }
 
-        for (target, path) in &self.get_state().current_paths {
-            if path.len() > 1 {
-                let distance_to_goal = Self::distance(target, &self.get_goal());
+            if let Some((from, to)) = state.active_edge {
+                let path = Path::line(
+                    from.to_canvas_with(options.y_down),
+                    to.to_canvas_with(options.y_down),
+                );
+                frame.stroke(
+                    &path,
+                    Stroke::default()
+                        .with_color(Color::from_rgb8(255, 215, 0))
+                        .with_width(3.0 * options.line_scale),
+                );
+            }
 
-                if distance_to_goal < best_distance_to_goal {
-                    best_distance_to_goal = distance_to_goal;
-                    best_current_path = Some(path.clone());
+            // Draw the search tree as parent pointers; the start has no parent
+            if options.show_search_tree {
+                let tree_stroke = Stroke::default()
+                    .with_color(dim(Color::from_rgba8(148, 0, 211, 0.6)))
+                    .with_width(1.5 * options.line_scale);
+
+                for (child, parent) in &state.came_from {
+                    if *child == self.get_start() {
+                        continue;
+                    }
+                    let path = Path::line(
+                        parent.to_canvas_with(options.y_down),
+                        child.to_canvas_with(options.y_down),
+                    );
+                    frame.stroke(&path, tree_stroke);
                 }
+            }
+
+            // Draw current active paths
+            let current_stroke = Stroke::default()
+                .with_color(dim(Color::from_rgba8(0, 100, 255, 0.5)))
+                .with_width(2.0 * options.line_scale);
+
+            // Find path closest to goal
+            let mut best_current_path = None;
+            let mut best_distance_to_goal = None;
+
+            for (target, path) in &state.current_paths {
+                if path.len() > 1 {
+                    let distance_to_goal = Self::distance(target, &self.get_goal());
+
+                    if best_distance_to_goal.is_none_or(|best| distance_to_goal < best) {
+                        best_distance_to_goal = Some(distance_to_goal);
+                        best_current_path = Some(path.clone());
+                    }
+
+                    for window in path.windows(2) {
+                        let from = window[0];
+                        let to = window[1];
+                        let path = Path::line(
+                            from.to_canvas_with(options.y_down),
+                            to.to_canvas_with(options.y_down),
+                        );
+                        frame.stroke(&path, current_stroke);
+                    }
+                }
+            }
+
+            if focus {
+                focus_path.clone_from(&best_current_path);
+            }
+
+            // Draw best current path
+            if let (Some(path), Some(best_distance_to_goal)) =
+                (best_current_path, best_distance_to_goal)
+            {
+                let best_stroke = Stroke::default()
+                    .with_color(Color::from_rgb8(50, 205, 50))
+                    .with_width(3.0 * options.line_scale);
 
                 for window in path.windows(2) {
                     let from = window[0];
                     let to = window[1];
                     let path = Path::line(
-                        (from.x as f32, -from.y as f32).into(),
-                        (to.x as f32, -to.y as f32).into(),
+                        from.to_canvas_with(options.y_down),
+                        to.to_canvas_with(options.y_down),
                     );
-                    frame.stroke(&path, current_stroke);
+                    frame.stroke(&path, best_stroke);
                 }
-            }
-        }
-
-        // Draw best current path
-        if let Some(path) = best_current_path {
-            let best_stroke = Stroke::default()
-                .with_color(Color::from_rgb8(50, 205, 50))
-                .with_width(3.0);
 
-            for window in path.windows(2) {
-                let from = window[0];
-                let to = window[1];
-                let path = Path::line(
-                    (from.x as f32, -from.y as f32).into(),
-                    (to.x as f32, -to.y as f32).into(),
-                );
-                frame.stroke(&path, best_stroke);
-            }
+                if let Some(last) = path.last() {
+                    let (current_path_score, best_distance_to_goal) = if options.precise_cost {
+                        (
+                            format!("{:.1}", Self::path_length_f64(&path)),
+                            format!("{:.1}", Self::path_length_f64(&[*last, self.get_goal()])),
+                        )
+                    } else {
+                        let score: T = path
+                            .windows(2)
+                            .map(|window| self.edge_cost(&window[0], &window[1]))
+                            .sum();
+                        (score.to_string(), best_distance_to_goal.to_string())
+                    };
 
-            if let Some(last) = path.last() {
-                let current_path_score: i32 = path
-                    .windows(2)
-                    .map(|window| Self::distance(&window[0], &window[1]))
-                    .sum();
-
-                let content = match best_distance_to_goal {
-                    0 => format!("Goal: {current_path_score}"),
-                    _ => format!(
-                        "Current best: {current_path_score}\nTo goal: {best_distance_to_goal}"
-                    ),
-                };
-                frame.fill_text(Text {
-                    content,
-                    position: (last.x as f32 + 2.5, -last.y as f32 + 2.5).into(),
-                    color: Color::BLACK,
-                    size: 4.0.into(),
-                    ..Text::default()
-                });
+                    let content = if *last == self.get_goal() {
+                        format!("Goal: {current_path_score}")
+                    } else {
+                        format!(
+                            "Current best: {current_path_score}\nTo goal: {best_distance_to_goal}"
+                        )
+                    };
+                    frame.fill_text(Text {
+                        content,
+                        position: last.to_canvas_with(options.y_down) + iced::Vector::new(2.5, 2.5),
+                        color: Color::BLACK,
+                        size: 4.0.into(),
+                        ..Text::default()
+                    });
+                }
             }
         }
 
-        // Draw optimal solution if requested
-        if show_solution {
+        // Draw optimal solution if requested; clean renders always show it
+        if options.show_solution || !full {
             if let Some((path, score)) = self.get_optimal_path() {
+                if let Some(width) = options.corridor_width {
+                    let narrow = self
+                        .get_board()
+                        .narrow_segments(path, f64::from(width) / 2.0);
+                    for (i, window) in path.windows(2).enumerate() {
+                        let color = if narrow.contains(&i) {
+                            Color::from_rgba8(220, 20, 60, 0.35)
+                        } else {
+                            Color::from_rgba8(50, 205, 50, 0.25)
+                        };
+                        let band = Stroke {
+                            line_cap: LineCap::Round,
+                            line_join: LineJoin::Round,
+                            ..Default::default()
+                        }
+                        .with_color(color)
+                        .with_width(width);
+                        frame.stroke(
+                            &Path::line(
+                                window[0].to_canvas_with(options.y_down),
+                                window[1].to_canvas_with(options.y_down),
+                            ),
+                            band,
+                        );
+                    }
+                }
+
                 let solution_stroke = Stroke {
                     line_dash: LineDash {
                         segments: &[5.0, 5.0],
@@ -231,22 +1013,28 @@ pub trait Pathfinder {
                     ..Default::default()
                 }
                 .with_color(Color::from_rgb8(50, 205, 50))
-                .with_width(3.0);
+                .with_width(3.0 * options.line_scale);
 
                 for window in path.windows(2) {
                     let from = window[0];
                     let to = window[1];
                     let path = Path::line(
-                        (from.x as f32, -from.y as f32).into(),
-                        (to.x as f32, -to.y as f32).into(),
+                        from.to_canvas_with(options.y_down),
+                        to.to_canvas_with(options.y_down),
                     );
                     frame.stroke(&path, solution_stroke);
                 }
 
                 if let Some(last) = path.last() {
+                    let score = if options.precise_cost {
+                        format!("{:.1}", Self::path_length_f64(path))
+                    } else {
+                        score.to_string()
+                    };
                     frame.fill_text(Text {
-                        content: format!("Optimal: {}", score),
-                        position: (last.x as f32 + 5.0, -last.y as f32 - 5.0).into(),
+                        content: format!("Optimal: {score}"),
+                        position: last.to_canvas_with(options.y_down)
+                            + iced::Vector::new(5.0, -5.0),
                         color: Color::BLACK,
                         size: 4.0.into(),
                         ..Text::default()
@@ -255,42 +1043,92 @@ pub trait Pathfinder {
             }
         }
 
-        // Draw vertices
-        for vertex in &self.get_state().open {
-            let circle = Path::circle((vertex.x as f32, -vertex.y as f32).into(), 1.0);
-            frame.fill(&circle, Fill::from(Color::from_rgb8(0, 100, 255)));
-        }
+        if full {
+            // Draw vertices, optionally sized by their normalized g-score so
+            // the cost landscape shows in relief
+            let g_scores = &state.g_scores;
+            let (min_g, max_g) = g_scores
+                .values()
+                .map(|g| -> f32 { g.as_() })
+                .fold((f32::MAX, f32::MIN), |(lo, hi), g| (lo.min(g), hi.max(g)));
+            let radius = |vertex: &Point<T>| {
+                let g = g_scores.get(vertex).map(|g| -> f32 { g.as_() });
+                let scale = match g {
+                    Some(g) if options.size_by_g_score && max_g > min_g => {
+                        let (min_radius, max_radius) = G_SCORE_RADIUS_RANGE;
+                        min_radius + (g - min_g) / (max_g - min_g) * (max_radius - min_radius)
+                    }
+                    _ => 1.0,
+                };
+                scale * options.line_scale
+            };
 
-        for vertex in &self.get_state().closed {
-            let circle = Path::circle((vertex.x as f32, -vertex.y as f32).into(), 1.0);
-            frame.fill(&circle, Fill::from(Color::from_rgb8(255, 100, 100)));
-        }
+            for vertex in state.open_sorted() {
+                let circle = Path::circle(vertex.to_canvas_with(options.y_down), radius(&vertex));
+                frame.fill(&circle, Fill::from(dim(Color::from_rgb8(0, 100, 255))));
+            }
 
-        if let Some(next) = self.get_state().next_vertex {
-            let circle = Path::circle((next.x as f32, -next.y as f32).into(), 1.5);
-            frame.fill(&circle, Fill::from(Color::from_rgb8(50, 205, 50)));
+            let last_expansion = state.expansion_order.len().saturating_sub(1).max(1) as f32;
+            for vertex in state.closed_sorted() {
+                let circle = Path::circle(vertex.to_canvas_with(options.y_down), radius(&vertex));
+                let color = match state.expansion_order.get(&vertex) {
+                    Some(&order) if options.expansion_gradient => {
+                        let (early, late) = EXPANSION_GRADIENT;
+                        let t = order as f32 / last_expansion;
+                        Color::from_rgb(
+                            early.r + (late.r - early.r) * t,
+                            early.g + (late.g - early.g) * t,
+                            early.b + (late.b - early.b) * t,
+                        )
+                    }
+                    _ => Color::from_rgb8(255, 100, 100),
+                };
+                frame.fill(&circle, Fill::from(dim(color)));
+            }
+
+            if let Some(next) = state.next_vertex {
+                let circle = Path::circle(
+                    next.to_canvas_with(options.y_down),
+                    1.5 * options.line_scale,
+                );
+                frame.fill(&circle, Fill::from(Color::from_rgb8(50, 205, 50)));
+            }
+
+            for vertex in focus_path.iter().flatten() {
+                let circle = Path::circle(
+                    vertex.to_canvas_with(options.y_down),
+                    1.5 * options.line_scale,
+                );
+                frame.fill(&circle, Fill::from(Color::from_rgb8(50, 205, 50)));
+            }
         }
 
         // Draw start and goal
         let start = self.get_start();
         let goal = self.get_goal();
 
-        let start_circle = Path::circle((start.x as f32, -start.y as f32).into(), 2.0);
+        let start_circle = Path::circle(
+            start.to_canvas_with(options.y_down),
+            2.0 * options.line_scale,
+        );
         frame.fill(&start_circle, Fill::from(Color::from_rgb8(0, 0, 255)));
         frame.fill_text(Text {
             content: format!("({}, {})", start.x, start.y),
-            position: (start.x as f32, -start.y as f32 - 6.5).into(),
+            position: start.to_canvas_with(options.y_down) + iced::Vector::new(0.0, -6.5),
             color: Color::BLACK,
             size: 4.0.into(),
             horizontal_alignment: iced::alignment::Horizontal::Center,
             ..Text::default()
         });
 
-        let goal_circle = Path::circle((goal.x as f32, -goal.y as f32).into(), 2.0);
+        let goal_circle = Path::circle(
+            goal.to_canvas_with(options.y_down),
+            2.0 * options.line_scale,
+        );
         frame.fill(&goal_circle, Fill::from(Color::from_rgb8(255, 0, 0)));
         frame.fill_text(Text {
             content: format!("({}, {})", goal.x, goal.y),
-            position: (goal.x as f32 - 2.5, -goal.y as f32 - 6.5).into(),
+            position: goal.to_canvas_with(options.y_down) + iced::Vector::new(-2.5, -6.5),
             color: Color::BLACK,
             size: 4.0.into(),
             horizontal_alignment: iced::alignment::Horizontal::Center,
@@ -298,3 +1136,170 @@ pub trait Pathfinder {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::sample_board;
+    use crate::search::AStarPathfinder;
+
+    #[test]
+    fn test_blend_heuristic() {
+        let (p1, p2) = (Point::new(3, -7), Point::new(40, 25));
+        let euclidean = Heuristic::Euclidean.distance(&p1, &p2);
+        let manhattan = Heuristic::Manhattan.distance(&p1, &p2);
+
+        assert_eq!(Heuristic::Blend(0.0).distance(&p1, &p2), euclidean);
+        assert_eq!(Heuristic::Blend(1.0).distance(&p1, &p2), manhattan);
+        assert_eq!(
+            Heuristic::Blend(-1.0).distance(&p1, &p2),
+            euclidean,
+            "Clamped"
+        );
+
+        let blend = Heuristic::Blend(0.4).distance(&p1, &p2);
+        assert!(euclidean < blend && blend < manhattan);
+    }
+
+    #[test]
+    fn test_explored_area() {
+        let board = sample_board();
+        let (start, goal) = (Point::new(115, 655), Point::new(380, 560));
+        let mut informed = AStarPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let mut dijkstra = AStarPathfinder::new(board, start, goal, Heuristic::Euclidean);
+        dijkstra.set_weight(0.0);
+
+        assert_eq!(informed.explored_area(), 0.0, "Only the start is open");
+
+        informed.jump_to(informed.total_steps());
+        dijkstra.jump_to(dijkstra.total_steps());
+        assert!(informed.explored_area() > 0.0);
+        assert!(
+            informed.explored_area() < dijkstra.explored_area(),
+            "{} vs {}",
+            informed.explored_area(),
+            dijkstra.explored_area()
+        );
+    }
+
+    #[test]
+    fn test_path_prefix() {
+        let path = [Point::new(0, 0), Point::new(30, 40), Point::new(30, 60)];
+        let prefix = |length| AStarPathfinder::path_prefix(&path, length);
+
+        assert_eq!(prefix(0.0), vec![Point::new(0, 0)]);
+        assert_eq!(prefix(25.0), vec![Point::new(0, 0), Point::new(15, 20)]);
+        assert_eq!(prefix(60.0), vec![path[0], path[1], Point::new(30, 50)]);
+        assert_eq!(prefix(1000.0), path);
+        assert!(AStarPathfinder::path_prefix(&[] as &[Point], 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_path_length_f64() {
+        let path = [Point::new(0, 0), Point::new(3, 4), Point::new(4, 5)];
+        let length = AStarPathfinder::path_length_f64(&path);
+
+        assert!((length - (5.0 + 2.0_f64.sqrt())).abs() < 1e-12);
+        assert_eq!(AStarPathfinder::distance(&path[1], &path[2]), 1, "Rounded");
+    }
+
+    #[test]
+    fn test_hausdorff_distance() {
+        let a = [Point::new(0, 0), Point::new(100, 0)];
+        let b = [Point::new(0, 0), Point::new(50, 30), Point::new(100, 0)];
+
+        assert_eq!(hausdorff_distance(&a, &a), 0.0);
+        assert_eq!(hausdorff_distance(&a, &b), 30.0);
+        assert_eq!(hausdorff_distance(&b, &a), 30.0);
+    }
+
+    #[test]
+    fn test_path_segment_diff() {
+        let a = [Point::new(0, 0), Point::new(50, 30), Point::new(100, 0)];
+        let (shared, only_a, only_b) = path_segment_diff(&a, &a);
+        assert_eq!(shared.len(), 2);
+        assert!(only_a.is_empty() && only_b.is_empty());
+
+        // Shares the first segment, walked backwards
+        let b = [
+            Point::new(50, 30),
+            Point::new(0, 0),
+            Point::new(0, 50),
+            Point::new(100, 0),
+        ];
+        let (shared, only_a, only_b) = path_segment_diff(&a, &b);
+        assert_eq!(shared, [Edge::new(a[0], a[1])]);
+        assert_eq!(only_a, [Edge::new(a[1], a[2])]);
+        assert_eq!(only_b, [Edge::new(b[1], b[2]), Edge::new(b[2], b[3])]);
+    }
+
+    #[test]
+    fn test_frontier_bounds() {
+        let mut search = AStarPathfinder::new(
+            sample_board(),
+            Point::new(0, 450),
+            Point::new(480, 740),
+            Heuristic::Euclidean,
+        );
+        search.jump_to(search.total_steps() / 2);
+
+        let (min, max) = search.frontier_bounds().unwrap();
+        let state = search.get_state();
+        assert!(!state.closed.is_empty());
+        for v in state.open.iter().chain(&state.closed) {
+            assert!(min.x <= v.x && v.x <= max.x && min.y <= v.y && v.y <= max.y);
+        }
+    }
+
+    #[test]
+    fn test_sorted_accessors_are_deterministic() {
+        let mut state = SearchState::new(Point::new(0, 0));
+        for i in 0..20 {
+            state.open.insert(Point::new(i * 7 % 11, i));
+            state.closed.insert(Point::new(i, i * 5 % 13));
+            state
+                .considered_edges
+                .insert((Point::new(i % 3, i), Point::new(i, i % 4)));
+        }
+
+        assert_eq!(state.open_sorted(), state.open_sorted());
+        assert_eq!(state.closed_sorted(), state.closed_sorted());
+        assert_eq!(
+            state.considered_edges_sorted(),
+            state.considered_edges_sorted()
+        );
+
+        let open = state.open_sorted();
+        assert!(
+            open.windows(2)
+                .all(|w| (w[0].x, w[0].y) <= (w[1].x, w[1].y)),
+            "Open vertices should be ordered by coordinate"
+        );
+    }
+
+    #[test]
+    fn test_within_cost() {
+        let (start, goal) = (Point::new(115, 655), Point::new(380, 560));
+        let search = AStarPathfinder::new(sample_board(), start, goal, Heuristic::Euclidean);
+        let last = search.history().last().unwrap();
+        let threshold = 150;
+
+        let filtered = last.within_cost(threshold);
+        let under = |set: &HashSet<Point>| -> HashSet<Point> {
+            set.iter()
+                .copied()
+                .filter(|v| last.g_scores[v] <= threshold)
+                .collect()
+        };
+
+        assert_eq!(filtered.open, under(&last.open));
+        assert_eq!(filtered.closed, under(&last.closed));
+        assert!(filtered.closed.contains(&start));
+        assert!(filtered.closed.len() < last.closed.len());
+        assert!(filtered
+            .considered_edges
+            .iter()
+            .all(|(from, to)| filtered.g_scores.contains_key(from)
+                && filtered.g_scores.contains_key(to)));
+    }
+}
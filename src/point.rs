@@ -22,11 +22,78 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 use crate::Vector;
 
-use num_traits::{Float, Num};
+use num_traits::{AsPrimitive, Float, Num, Signed};
 use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A scalar type usable for [`Point`] coordinates throughout the search.
+///
+/// Implemented for the signed integer and floating point primitives. Floats
+/// are hashed by their bit pattern so that points can key a `HashMap`.
+pub trait Coordinate:
+    Copy
+    + Default
+    + fmt::Debug
+    + fmt::Display
+    + PartialOrd
+    + Signed
+    + std::iter::Sum
+    + AsPrimitive<f32>
+    + AsPrimitive<f64>
+{
+    /// Converts from an `f64`, truncating for integer types.
+    fn from_f64(value: f64) -> Self;
+
+    /// Converts from an `f64`, rounding to the nearest value for integer
+    /// types.
+    fn round_f64(value: f64) -> Self;
+
+    /// Feeds the coordinate into the given [`Hasher`].
+    fn hash_coordinate<H: Hasher>(&self, state: &mut H);
+}
+
+macro_rules! impl_integer_coordinate {
+    ($($t:ty),*) => {$(
+        impl Coordinate for $t {
+            fn from_f64(value: f64) -> Self {
+                value as $t
+            }
+
+            fn round_f64(value: f64) -> Self {
+                value.round() as $t
+            }
+
+            fn hash_coordinate<H: Hasher>(&self, state: &mut H) {
+                self.hash(state);
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_float_coordinate {
+    ($($t:ty),*) => {$(
+        impl Coordinate for $t {
+            fn from_f64(value: f64) -> Self {
+                value as $t
+            }
+
+            fn round_f64(value: f64) -> Self {
+                value as $t
+            }
+
+            fn hash_coordinate<H: Hasher>(&self, state: &mut H) {
+                // Adding zero folds -0.0 into 0.0 so equal values hash equally
+                (*self + 0.0).to_bits().hash(state);
+            }
+        }
+    )*};
+}
+
+impl_integer_coordinate!(i32, i64);
+impl_float_coordinate!(f32, f64);
 
 /// A 2D point.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Point<T = i32> {
     /// The X coordinate.
     pub x: T,
@@ -58,6 +125,70 @@ impl<T: Num> Point<T> {
     }
 }
 
+impl<T: Coordinate> Point<T> {
+    /// Converts to canvas coordinates, flipping y to match mathematical
+    /// coordinates.
+    pub fn to_canvas(self) -> iced::Point {
+        self.to_canvas_with(false)
+    }
+
+    /// Converts to canvas coordinates, flipping y unless `y_down` is set, for
+    /// data that already uses the screen convention of y growing downward
+    pub fn to_canvas_with(self, y_down: bool) -> iced::Point {
+        let x: f32 = self.x.as_();
+        let y: f32 = self.y.as_();
+        iced::Point::new(x, if y_down { y } else { -y })
+    }
+
+    /// Returns true if both coordinates are within `eps` of the other
+    /// [`Point`]'s, e.g. to treat float points that only differ by rounding
+    /// error as the same
+    pub fn approx_eq(&self, other: &Self, eps: T) -> bool {
+        num_traits::abs(self.x - other.x) <= eps && num_traits::abs(self.y - other.y) <= eps
+    }
+
+    /// Scales the [`Point`] by `factor` about another [`Point`], rounding to
+    /// the nearest integer for integer coordinates.
+    pub fn scaled(self, factor: f32, about: Self) -> Self {
+        let scale = |value: T, origin: T| {
+            let value: f64 = value.as_();
+            let origin: f64 = origin.as_();
+            T::round_f64(origin + (value - origin) * factor as f64)
+        };
+
+        Self::new(scale(self.x, about.x), scale(self.y, about.y))
+    }
+
+    /// Scales the [`Point`] by `factor` about a pivot. This is
+    /// [`Point::scaled`] with the pivot first, like every `_about` transform.
+    pub fn scaled_about(&self, pivot: &Self, factor: f32) -> Self {
+        self.scaled(factor, *pivot)
+    }
+
+    /// Rotates the [`Point`] counterclockwise by `radians` about a pivot,
+    /// rounding to the nearest integer for integer coordinates.
+    pub fn rotated_about(&self, pivot: &Self, radians: f32) -> Self {
+        let (sin, cos) = f64::from(radians).sin_cos();
+        let (px, py): (f64, f64) = (pivot.x.as_(), pivot.y.as_());
+        let dx: f64 = (self.x - pivot.x).as_();
+        let dy: f64 = (self.y - pivot.y).as_();
+
+        Self::new(
+            T::round_f64(px + dx * cos - dy * sin),
+            T::round_f64(py + dx * sin + dy * cos),
+        )
+    }
+}
+
+impl<T: Coordinate> Eq for Point<T> {}
+
+impl<T: Coordinate> Hash for Point<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash_coordinate(state);
+        self.y.hash_coordinate(state);
+    }
+}
+
 impl<T> From<[T; 2]> for Point<T>
 where
     T: Num,
@@ -139,3 +270,41 @@ impl<T> From<Point<T>> for iced::Point<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotated_about() {
+        let point = Point::new(10, 0);
+        let quarter = std::f32::consts::FRAC_PI_2;
+        assert_eq!(
+            point.rotated_about(&Point::ORIGIN, quarter),
+            Point::new(0, 10)
+        );
+        assert_eq!(
+            point.rotated_about(&Point::ORIGIN, -quarter),
+            Point::new(0, -10)
+        );
+        assert_eq!(point.rotated_about(&point, quarter), point);
+        assert_eq!(
+            point.rotated_about(&Point::new(10, 10), std::f32::consts::PI),
+            Point::new(10, 20)
+        );
+    }
+
+    #[test]
+    fn test_scaled_about() {
+        let pivot = Point::new(10, 20);
+        assert_eq!(
+            Point::new(15, 10).scaled_about(&pivot, 2.0),
+            Point::new(20, 0)
+        );
+        assert_eq!(
+            Point::new(15, 10).scaled_about(&pivot, 0.5),
+            Point::new(13, 15)
+        );
+        assert_eq!(pivot.scaled_about(&pivot, 3.0), pivot);
+    }
+}
@@ -1,11 +1,13 @@
-use iced::widget::canvas::{Fill, Frame, Path, Stroke, Text};
+use iced::widget::canvas::{fill, Fill, Frame, Path, Stroke, Text};
 use iced::{color, Color};
 use palette::{Darken, Srgba};
 
-use crate::Point;
+use num_traits::AsPrimitive;
+
+use crate::{Coordinate, Point};
 
 /// Static slice of pastelish colors for drawing polygons. Thanks, ChatGPT!
-const COLORS: [Color; 16] = [
+pub(crate) const COLORS: [Color; 16] = [
     color!(255, 179, 186), // Light Pink
     color!(255, 223, 186), // Peach
     color!(255, 255, 186), // Light Yellow
@@ -24,8 +26,11 @@ const COLORS: [Color; 16] = [
     color!(186, 255, 223), // Aqua Mint
 ];
 
+/// Fill for polygons excluded from the search
+const DISABLED_COLOR: Color = Color::from_rgba(0.75, 0.75, 0.75, 0.4);
+
 /// Darkens a given [`Color`] by a percentage
-fn darken(color: Color, factor: f32) -> Color {
+pub(crate) fn darken(color: Color, factor: f32) -> Color {
     let srgba: Srgba = color.into();
     let darkened = srgba.darken(factor);
     Color::from(darkened)
@@ -34,45 +39,318 @@ fn darken(color: Color, factor: f32) -> Color {
 /// Represents a convex [`Polygon`] obstacle on the board.
 ///
 /// Vertices are stored in clockwise or counter-clockwise order.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Polygon {
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon<T = i32> {
     /// The vertices that make up the [`Polygon`], stored in order
-    vertices: Vec<Point>,
+    vertices: Vec<Point<T>>,
 }
 
-impl Polygon {
+impl<T: Coordinate> Eq for Polygon<T> {}
+
+impl<T: Coordinate> Polygon<T> {
     /// Creates a new [`Polygon`] from a vector of [`Point`]s
-    pub fn new(vertices: Vec<Point>) -> Self {
+    pub fn new(vertices: Vec<Point<T>>) -> Self {
         Self { vertices }
     }
 
+    /// Builds the convex hull of a set of [`Point`]s using Andrew's monotone
+    /// chain, with vertices in counter-clockwise order starting from the
+    /// lowest-x point.
+    ///
+    /// Duplicate points and points lying along a hull edge are dropped, so
+    /// collinear input yields a degenerate two-vertex [`Polygon`] spanning the
+    /// extreme points.
+    pub fn convex_hull(points: &[Point<T>]) -> Self {
+        let by = |a: T, b: T| a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal);
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| by(a.x, b.x).then(by(a.y, b.y)));
+        points.dedup();
+
+        if points.len() < 3 {
+            return Self::new(points);
+        }
+
+        // Positive when o -> a -> b turns counter-clockwise
+        let cross = |o: &Point<T>, a: &Point<T>, b: &Point<T>| -> f64 {
+            let (ox, oy): (f64, f64) = (o.x.as_(), o.y.as_());
+            let (ax, ay): (f64, f64) = (a.x.as_(), a.y.as_());
+            let (bx, by): (f64, f64) = (b.x.as_(), b.y.as_());
+            (ax - ox) * (by - oy) - (ay - oy) * (bx - ox)
+        };
+
+        let mut hull: Vec<Point<T>> = Vec::with_capacity(points.len() * 2);
+
+        // Lower hull, then upper hull, each stopping short of its last point
+        // since that point starts the other half
+        for pass in [
+            points.as_slice(),
+            &points.iter().rev().copied().collect::<Vec<_>>(),
+        ] {
+            let floor = hull.len();
+            for point in pass {
+                while hull.len() >= floor + 2
+                    && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], point) <= 0.0
+                {
+                    hull.pop();
+                }
+                hull.push(*point);
+            }
+            hull.pop();
+        }
+
+        Self::new(hull)
+    }
+
     /// Compute the center [`Point`] of the [`Polygon`] as the average of its
     /// vertices
-    pub fn center(&self) -> Point {
-        let n = self.vertices.len() as i32;
-        let mut x = 0;
-        let mut y = 0;
+    pub fn center(&self) -> Point<T> {
+        let n = T::from_f64(self.vertices.len() as f64);
+        let mut x = T::zero();
+        let mut y = T::zero();
 
         for vertex in &self.vertices {
-            x += vertex.x;
-            y += vertex.y;
+            x = x + vertex.x;
+            y = y + vertex.y;
         }
 
         Point::new(x / n, y / n)
     }
 
     /// Returns an iterator over the vertices of the [`Polygon`]
-    pub fn vertices(&self) -> impl Iterator<Item = &Point> {
+    pub fn vertices(&self) -> impl Iterator<Item = &Point<T>> {
         self.vertices.iter()
     }
 
     /// Returns all vertices as a vector of [`Point`]s
-    pub fn vertices_vec(&self) -> Vec<Point> {
+    pub fn vertices_vec(&self) -> Vec<Point<T>> {
         self.vertices.clone()
     }
 
+    /// Computes the area of the [`Polygon`] using the shoelace formula
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// Shoelace area, positive when the vertices run counter-clockwise
+    fn signed_area(&self) -> f64 {
+        let twice_area: f64 = self
+            .edges_iter()
+            .map(|e| {
+                let (sx, sy): (f64, f64) = (e.start.x.as_(), e.start.y.as_());
+                let (ex, ey): (f64, f64) = (e.end.x.as_(), e.end.y.as_());
+                sx * ey - ex * sy
+            })
+            .sum();
+
+        twice_area / 2.0
+    }
+
+    /// Returns the same [`Polygon`] with its vertices in counter-clockwise
+    /// order, starting from the lowest-x, then lowest-y vertex, so polygons
+    /// with the same outline compare equal however they were listed
+    pub fn canonical_form(&self) -> Self {
+        let by = |a: T, b: T| a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal);
+        let mut vertices = self.vertices.clone();
+        if self.signed_area() < 0.0 {
+            vertices.reverse();
+        }
+
+        let first = vertices
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| by(a.x, b.x).then(by(a.y, b.y)))
+            .map_or(0, |(i, _)| i);
+        vertices.rotate_left(first);
+
+        Self { vertices }
+    }
+
+    /// Returns the minimum and maximum corners of the axis-aligned box
+    /// enclosing the [`Polygon`]. An empty [`Polygon`] has a zero-sized box at
+    /// the origin.
+    pub fn bounding_box(&self) -> (Point<T>, Point<T>) {
+        let Some(first) = self.vertices.first() else {
+            return (Point::default(), Point::default());
+        };
+
+        let min = |a: T, b: T| if b < a { b } else { a };
+        let max = |a: T, b: T| if b > a { b } else { a };
+
+        self.vertices.iter().fold((*first, *first), |(lo, hi), v| {
+            (
+                Point::new(min(lo.x, v.x), min(lo.y, v.y)),
+                Point::new(max(hi.x, v.x), max(hi.y, v.y)),
+            )
+        })
+    }
+
+    /// Returns the Euclidean distance from a [`Point`] to the [`Polygon`]'s
+    /// boundary, or 0.0 if the point lies inside it
+    pub fn distance_to_point(&self, point: &Point<T>) -> f64 {
+        if self.contains_point(point) {
+            return 0.0;
+        }
+
+        self.edges_iter()
+            .map(|edge| edge.distance_to_point(point))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Returns true if any vertex lies within `radius` of one of the `points`
+    pub fn is_near(&self, points: &[Point<T>], radius: f64) -> bool {
+        self.vertices.iter().any(|v| {
+            points.iter().any(|p| {
+                let dx: f64 = (v.x - p.x).as_();
+                let dy: f64 = (v.y - p.y).as_();
+                dx * dx + dy * dy <= radius * radius
+            })
+        })
+    }
+
+    /// Returns each outer [`Edge`] translated outward along its normal by
+    /// `distance`, rounding to the nearest integer for integer coordinates.
+    /// Works for either winding order.
+    pub fn offset_edges(&self, distance: f64) -> Vec<Edge<T>> {
+        // Positive for counter-clockwise winding, whose outward normals point
+        // to the right of each edge
+        let winding: f64 = self
+            .edges_iter()
+            .map(|e| {
+                let (sx, sy): (f64, f64) = (e.start.x.as_(), e.start.y.as_());
+                let (ex, ey): (f64, f64) = (e.end.x.as_(), e.end.y.as_());
+                sx * ey - ex * sy
+            })
+            .sum::<f64>()
+            .signum();
+
+        self.edges_iter()
+            .map(|edge| {
+                let dx: f64 = (edge.end.x - edge.start.x).as_();
+                let dy: f64 = (edge.end.y - edge.start.y).as_();
+                let length = dx.hypot(dy);
+                if length == 0.0 {
+                    return edge;
+                }
+
+                let (nx, ny) = (winding * dy / length, -winding * dx / length);
+                let shift = |p: Point<T>| {
+                    let (x, y): (f64, f64) = (p.x.as_(), p.y.as_());
+                    Point::new(
+                        T::round_f64(x + nx * distance),
+                        T::round_f64(y + ny * distance),
+                    )
+                };
+
+                Edge::new(shift(edge.start), shift(edge.end))
+            })
+            .collect()
+    }
+
+    /// Returns a copy of the [`Polygon`] scaled by `factor` about a [`Point`]
+    pub fn scaled(&self, factor: f32, about: Point<T>) -> Self {
+        Self::new(
+            self.vertices
+                .iter()
+                .map(|v| v.scaled(factor, about))
+                .collect(),
+        )
+    }
+
+    /// Returns a copy of the [`Polygon`] rotated counterclockwise by `radians`
+    /// about a pivot, see [`Point::rotated_about`]
+    pub fn rotated_about(&self, pivot: &Point<T>, radians: f32) -> Self {
+        Self::new(
+            self.vertices
+                .iter()
+                .map(|v| v.rotated_about(pivot, radians))
+                .collect(),
+        )
+    }
+
+    /// Returns a copy of the [`Polygon`] with vertices inserted evenly along
+    /// each edge longer than `max_edge_len`, leaving its shape unchanged.
+    ///
+    /// Points that can't be placed exactly on their edge, e.g. on integer
+    /// coordinates that don't divide evenly, are skipped, so some edges may
+    /// remain longer than `max_edge_len`. A length that isn't positive leaves
+    /// the [`Polygon`] as is.
+    pub fn densified(&self, max_edge_len: f64) -> Self {
+        if max_edge_len.is_nan() || max_edge_len <= 0.0 {
+            return self.clone();
+        }
+
+        let mut vertices = Vec::with_capacity(self.vertices.len());
+
+        for edge in self.edges_iter() {
+            vertices.push(edge.start);
+
+            let (dx, dy): (f64, f64) = (
+                (edge.end.x - edge.start.x).as_(),
+                (edge.end.y - edge.start.y).as_(),
+            );
+            let pieces = (dx.hypot(dy) / max_edge_len).ceil() as usize;
+            for k in 1..pieces {
+                let t = k as f64 / pieces as f64;
+                let (sx, sy): (f64, f64) = (edge.start.x.as_(), edge.start.y.as_());
+                let point = Point::new(T::round_f64(sx + t * dx), T::round_f64(sy + t * dy));
+                if edge.contains_point(&point) {
+                    vertices.push(point);
+                }
+            }
+        }
+
+        Self::new(vertices)
+    }
+
+    /// Returns a copy of the [`Polygon`] with the vertices within `epsilon`
+    /// of its simplified outline dropped, using Douglas–Peucker.
+    ///
+    /// The outline is split at the first vertex and the one farthest from
+    /// it, and each half is simplified on its own, so every dropped vertex
+    /// stays within `epsilon` of the result. If simplifying would leave fewer
+    /// than three vertices or make the outline self-intersect, the
+    /// [`Polygon`] is returned as is.
+    pub fn simplified(&self, epsilon: f64) -> Self {
+        let n = self.vertices.len();
+        if n <= 3 || epsilon.is_nan() || epsilon < 0.0 {
+            return self.clone();
+        }
+
+        let first = self.vertices[0];
+        let split = (1..n)
+            .max_by(|&i, &j| {
+                let distance = |k: usize| {
+                    let dx: f64 = (self.vertices[k].x - first.x).as_();
+                    let dy: f64 = (self.vertices[k].y - first.y).as_();
+                    dx.hypot(dy)
+                };
+                distance(i).total_cmp(&distance(j))
+            })
+            .unwrap_or(0);
+
+        let mut keep = vec![false; n];
+        keep[0] = true;
+        keep[split] = true;
+        let ring: Vec<Point<T>> = self.vertices.iter().chain([&first]).copied().collect();
+        douglas_peucker(&ring, 0, split, epsilon, &mut keep);
+        douglas_peucker(&ring, split, n, epsilon, &mut keep);
+
+        let vertices: Vec<Point<T>> = self
+            .vertices
+            .iter()
+            .zip(keep)
+            .filter_map(|(vertex, keep)| keep.then_some(*vertex))
+            .collect();
+        let simplified = Self::new(vertices);
+
+        if simplified.vertices.len() < 3 || !simplified.is_simple() {
+            return self.clone();
+        }
+        simplified
+    }
+
     /// Returns the outer [`Edge`]s of the [`Polygon`] as directed edges
-    pub fn outer_edges(&self) -> Vec<Edge> {
+    pub fn outer_edges(&self) -> Vec<Edge<T>> {
         let vertices = &self.vertices;
         let n = vertices.len();
         let mut edges = Vec::with_capacity(n);
@@ -86,8 +364,58 @@ impl Polygon {
         edges
     }
 
+    /// Lazily yields the outer [`Edge`]s of the [`Polygon`] in the same order
+    /// as [`Polygon::outer_edges`], without allocating
+    pub fn edges_iter(&self) -> impl Iterator<Item = Edge<T>> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| Edge::new(self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    /// Lazily yields the outer [`Edge`]s like [`Polygon::edges_iter`], each with
+    /// the indices of the vertices it starts and ends at. The last edge wraps
+    /// around to the first vertex.
+    pub fn edges_indexed(&self) -> impl Iterator<Item = (usize, usize, Edge<T>)> + '_ {
+        let n = self.vertices.len();
+        self.edges_iter()
+            .enumerate()
+            .map(move |(i, edge)| (i, (i + 1) % n, edge))
+    }
+
+    /// Returns true if no two non-adjacent edges of the [`Polygon`] touch or
+    /// cross
+    pub fn is_simple(&self) -> bool {
+        let edges = self.outer_edges();
+        let n = edges.len();
+
+        (0..n).all(|i| {
+            // Adjacent edges share an endpoint, which `Edge::intersects` skips
+            (i + 2..n).all(|j| !edges[i].intersects(&edges[j]))
+        })
+    }
+
+    /// Returns true if the interiors of two [`Polygon`]s overlap, i.e. their
+    /// edges cross or one has a vertex or its center inside the other.
+    /// Polygons that only share vertices or touch along an edge don't overlap.
+    pub fn overlaps(&self, other: &Polygon<T>) -> bool {
+        let crosses = self
+            .edges_iter()
+            .any(|a| other.edges_iter().any(|b| a.intersects(&b)));
+        let inside = |a: &Polygon<T>, b: &Polygon<T>| {
+            a.vertices()
+                .chain([&a.center()])
+                .any(|v| b.contains_point(v))
+        };
+
+        crosses || inside(self, other) || inside(other, self)
+    }
+
     /// Determine if a line segment intersects with the [`Polygon`]
-    pub fn intersects_segment(&self, start: &Point, end: &Point) -> bool {
+    ///
+    /// A chord between two of the polygon's own vertices crosses no edge, so
+    /// it's blocked only if its midpoint lies strictly inside: a square's
+    /// diagonal is blocked, while a side or a chord across a concave notch is
+    /// not.
+    pub fn intersects_segment(&self, start: &Point<T>, end: &Point<T>) -> bool {
         let n = self.vertices.len();
         let test_edge = Edge::new(*start, *end);
 
@@ -128,60 +456,95 @@ impl Polygon {
             return true;
         }
 
-        // Check midpoint
-        let mid = Point::new((start.x + end.x) / 2, (start.y + end.y) / 2);
-        !test_edge.contains_point(&mid) && self.contains_point(&mid)
+        // Check midpoint, which catches chords between non-adjacent vertices
+        let two = T::one() + T::one();
+        let mid = Point::new((start.x + end.x) / two, (start.y + end.y) / two);
+        let on_boundary = self.edges_iter().any(|e| e.contains_point(&mid));
+        !on_boundary && self.contains_point(&mid)
     }
 
-    /// Checks if a point lies inside the polygon using the ray casting algorithm
-    fn contains_point(&self, point: &Point) -> bool {
-        let mut inside = false;
-        let mut j = self.vertices.len() - 1;
-
-        for i in 0..self.vertices.len() {
-            let vi = &self.vertices[i];
-            let vj = &self.vertices[j];
-
-            // Check if point is exactly on a vertex
-            if point == vi || point == vj {
-                return false; // Consider points on vertices as outside
+    /// Checks if a point lies strictly inside the polygon using the winding
+    /// number algorithm. Points on the boundary, including vertices, are
+    /// considered outside.
+    ///
+    /// Only orientation tests are used, so horizontal edges and points level
+    /// with a vertex need no special casing and nothing is ever divided.
+    pub fn contains_point(&self, point: &Point<T>) -> bool {
+        let mut winding = 0;
+
+        for edge in self.edges_iter() {
+            if edge.contains_point(point) {
+                return false;
             }
 
-            if ((vi.y > point.y) != (vj.y > point.y))
-                && (point.x < (vj.x - vi.x) * (point.y - vi.y) / (vj.y - vi.y) + vi.x)
-            {
-                inside = !inside;
-            }
+            let (a, b) = (edge.start, edge.end);
+            // Positive when the point is left of the edge a -> b
+            let orientation = (b.x - a.x) * (point.y - a.y) - (point.x - a.x) * (b.y - a.y);
 
-            j = i;
+            if a.y <= point.y {
+                // Upward crossing with the point on the left
+                if b.y > point.y && orientation > T::zero() {
+                    winding += 1;
+                }
+            } else if b.y <= point.y && orientation < T::zero() {
+                // Downward crossing with the point on the right
+                winding -= 1;
+            }
         }
 
-        inside
+        winding != 0
     }
 
-    /// Draw the [`Polygon`] on a canvas [`Frame`] at a given index
-    pub fn draw(&self, index: usize, frame: &mut Frame) {
-        let fill_color = COLORS[index % COLORS.len()];
+    /// Draw the [`Polygon`] on a canvas [`Frame`] at a given index, filling
+    /// it with the given [`fill::Rule`]. Both rules fill simple polygons the
+    /// same way; they only differ for self-overlapping outlines and holes.
+    /// See [`Point::to_canvas_with`] for `y_down`. Polygons that aren't
+    /// `enabled` are drawn in translucent grey.
+    pub fn draw(
+        &self,
+        index: usize,
+        frame: &mut Frame,
+        line_scale: f32,
+        fill_rule: fill::Rule,
+        y_down: bool,
+        enabled: bool,
+    ) {
+        let fill_color = if enabled {
+            COLORS[index % COLORS.len()]
+        } else {
+            DISABLED_COLOR
+        };
         let stroke_color = darken(fill_color, 0.5);
 
         let path = Path::new(|p| {
             for (i, vertex) in self.vertices.iter().enumerate() {
                 if i == 0 {
-                    p.move_to((vertex.x as f32, -vertex.y as f32).into());
+                    p.move_to(vertex.to_canvas_with(y_down));
                 } else {
-                    p.line_to((vertex.x as f32, -vertex.y as f32).into());
+                    p.line_to(vertex.to_canvas_with(y_down));
                 }
             }
             p.close();
         });
 
-        frame.fill(&path, Fill::from(fill_color));
-        frame.stroke(&path, Stroke::default().with_color(stroke_color));
+        frame.fill(
+            &path,
+            Fill {
+                rule: fill_rule,
+                ..Fill::from(fill_color)
+            },
+        );
+        frame.stroke(
+            &path,
+            Stroke::default()
+                .with_color(stroke_color)
+                .with_width(line_scale),
+        );
 
         let center = self.center();
         frame.fill_text(Text {
             content: format!("{}", index + 1),
-            position: (center.x as f32, -center.y as f32).into(),
+            position: center.to_canvas_with(y_down),
             color: Color::BLACK,
             size: 5.0.into(),
             ..Text::default()
@@ -190,20 +553,29 @@ impl Polygon {
 }
 
 /// Represents a directed [`Edge`] between two [`Point`]s
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Edge {
-    pub start: Point,
-    pub end: Point,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Edge<T = i32> {
+    pub start: Point<T>,
+    pub end: Point<T>,
+}
+
+impl<T: Coordinate> Eq for Edge<T> {}
+
+impl<T: Coordinate> std::hash::Hash for Edge<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.start.hash(state);
+        self.end.hash(state);
+    }
 }
 
-impl Edge {
-    pub fn new(start: Point, end: Point) -> Self {
+impl<T: Coordinate> Edge<T> {
+    pub fn new(start: Point<T>, end: Point<T>) -> Self {
         Self { start, end }
     }
 
     /// Returns true if this edge intersects with another edge,
     /// excluding edges that share an endpoint
-    pub fn intersects(&self, other: &Edge) -> bool {
+    pub fn intersects(&self, other: &Edge<T>) -> bool {
         // Skip if edges share an endpoint
         if self.start == other.start
             || self.start == other.end
@@ -230,7 +602,7 @@ impl Edge {
         let d = (k1 * k2) - (k3 * k4);
 
         // If d is 0, lines are parallel
-        if d == 0 {
+        if d.is_zero() {
             // For parallel lines, check if they're collinear and overlapping
             // using our existing contains_point method
             return self.contains_point(&other.start)
@@ -240,28 +612,110 @@ impl Edge {
         }
 
         // Calculate intersection parameters
-        let a = ((k2 * k5) - (k4 * k6)) as f64 / d as f64;
-        let b = ((k1 * k6) - (k3 * k5)) as f64 / d as f64;
+        let d: f64 = d.as_();
+        let a = AsPrimitive::<f64>::as_((k2 * k5) - (k4 * k6)) / d;
+        let b = AsPrimitive::<f64>::as_((k1 * k6) - (k3 * k5)) / d;
 
         // Lines intersect if both parameters are between 0 and 1
         (0.0..=1.0).contains(&a) && (0.0..=1.0).contains(&b)
     }
 
     /// Returns true if a point lies on this edge
-    pub fn contains_point(&self, point: &Point) -> bool {
+    pub fn contains_point(&self, point: &Point<T>) -> bool {
         // Check if point is collinear with edge endpoints
         let cross = (point.y - self.start.y) * (self.end.x - self.start.x)
             - (point.x - self.start.x) * (self.end.y - self.start.y);
 
-        if cross != 0 {
+        if !cross.is_zero() {
             return false;
         }
 
         // Check if point lies within the bounding box of the edge
-        point.x >= self.start.x.min(self.end.x)
-            && point.x <= self.start.x.max(self.end.x)
-            && point.y >= self.start.y.min(self.end.y)
-            && point.y <= self.start.y.max(self.end.y)
+        between(point.x, self.start.x, self.end.x) && between(point.y, self.start.y, self.end.y)
+    }
+
+    /// Returns the [`Point`] where this edge crosses another, rounded to the
+    /// nearest integer for integer coordinates, or `None` if they don't cross.
+    /// Parallel edges never cross, even when they overlap.
+    pub fn intersection_point(&self, other: &Edge<T>) -> Option<Point<T>> {
+        let (px, py): (f64, f64) = (self.start.x.as_(), self.start.y.as_());
+        let (qx, qy): (f64, f64) = (other.start.x.as_(), other.start.y.as_());
+        let (rx, ry): (f64, f64) = (
+            (self.end.x - self.start.x).as_(),
+            (self.end.y - self.start.y).as_(),
+        );
+        let (sx, sy): (f64, f64) = (
+            (other.end.x - other.start.x).as_(),
+            (other.end.y - other.start.y).as_(),
+        );
+
+        let denominator = rx * sy - ry * sx;
+        if denominator == 0.0 {
+            return None;
+        }
+
+        // Solve start + t * r == other.start + u * s for both parameters
+        let t = ((qx - px) * sy - (qy - py) * sx) / denominator;
+        let u = ((qx - px) * ry - (qy - py) * rx) / denominator;
+
+        ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u))
+            .then(|| Point::new(T::round_f64(px + t * rx), T::round_f64(py + t * ry)))
+    }
+
+    /// Returns the Euclidean distance from a [`Point`] to the closest point
+    /// on this edge, clamping the projection to the endpoints
+    pub fn distance_to_point(&self, p: &Point<T>) -> f64 {
+        // Work in f64 so squared lengths can't overflow integer coordinates
+        let (sx, sy): (f64, f64) = (self.start.x.as_(), self.start.y.as_());
+        let (ex, ey): (f64, f64) = (self.end.x.as_(), self.end.y.as_());
+        let (px, py): (f64, f64) = (p.x.as_(), p.y.as_());
+
+        let (dx, dy) = (ex - sx, ey - sy);
+        let length_squared = dx * dx + dy * dy;
+
+        // Degenerate edge: both endpoints coincide
+        if length_squared == 0.0 {
+            return (px - sx).hypot(py - sy);
+        }
+
+        let t = (((px - sx) * dx + (py - sy) * dy) / length_squared).clamp(0.0, 1.0);
+        (px - (sx + t * dx)).hypot(py - (sy + t * dy))
+    }
+}
+
+/// Marks the vertices strictly between `start` and `end` to keep, recursing
+/// on the one farthest from the segment joining them while it's farther than
+/// `epsilon`
+fn douglas_peucker<T: Coordinate>(
+    points: &[Point<T>],
+    start: usize,
+    end: usize,
+    epsilon: f64,
+    keep: &mut [bool],
+) {
+    if end <= start + 1 {
+        return;
+    }
+
+    let chord = Edge::new(points[start], points[end]);
+    let (farthest, distance) = (start + 1..end)
+        .map(|i| (i, chord.distance_to_point(&points[i])))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((start, 0.0));
+
+    if distance > epsilon {
+        keep[farthest] = true;
+        douglas_peucker(points, start, farthest, epsilon, keep);
+        douglas_peucker(points, farthest, end, epsilon, keep);
+    }
+}
+
+/// Returns true if `value` lies between `a` and `b`, inclusive, in either order
+fn between<T: PartialOrd>(value: T, a: T, b: T) -> bool {
+    if a <= b {
+        a <= value && value <= b
+    } else {
+        b <= value && value <= a
     }
 }
 
@@ -366,6 +820,27 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_edge_distance_to_point() {
+            let edge = Edge::new(Point::new(0, 0), Point::new(10, 0));
+
+            assert_eq!(
+                edge.distance_to_point(&Point::new(5, 5)),
+                5.0,
+                "Distance should be measured perpendicular to the edge"
+            );
+            assert_eq!(
+                edge.distance_to_point(&Point::new(13, 4)),
+                5.0,
+                "Distance beyond an endpoint should be to that endpoint"
+            );
+            assert_eq!(
+                edge.distance_to_point(&Point::new(5, 0)),
+                0.0,
+                "Point on the edge should have zero distance"
+            );
+        }
+
         #[test]
         fn test_edge_contains_point() {
             let edge = Edge::new(Point::new(0, 0), Point::new(10, 10));
@@ -438,6 +913,37 @@ mod tests {
             });
         }
 
+        #[test]
+        fn test_chord_cases() {
+            let square = create_square();
+            let corners = square.vertices_vec();
+            assert!(
+                square.intersects_segment(&corners[0], &corners[2]),
+                "Diagonal of a square should intersect"
+            );
+            assert!(
+                !square.intersects_segment(&corners[0], &corners[1]),
+                "Side of a square should not intersect"
+            );
+
+            // A notch between the top corners, bridged by the convex hull
+            let notched = Polygon::new(vec![
+                Point::new(0, 0),
+                Point::new(100, 0),
+                Point::new(100, 100),
+                Point::new(50, 50),
+                Point::new(0, 100),
+            ]);
+            assert!(
+                !notched.intersects_segment(&Point::new(0, 100), &Point::new(100, 100)),
+                "Convex hull chord across the notch should not intersect"
+            );
+            assert!(
+                notched.intersects_segment(&Point::new(0, 0), &Point::new(50, 50)),
+                "Chord through the interior should intersect"
+            );
+        }
+
         #[test]
         fn test_crossing_cases() {
             test_all_polygons(|polygon| {
@@ -501,6 +1007,47 @@ mod tests {
             });
         }
 
+        #[test]
+        fn test_contains_point_level_with_horizontal_edge() {
+            let square = create_square();
+
+            assert!(
+                !square.contains_point(&Point::new(150, 0)),
+                "Point level with the bottom edge but beyond it is outside"
+            );
+            assert!(
+                !square.contains_point(&Point::new(-50, 100)),
+                "Point level with the top edge but before it is outside"
+            );
+            assert!(
+                !square.contains_point(&Point::new(50, 0)),
+                "Point on a horizontal edge is on the boundary"
+            );
+            assert!(square.contains_point(&Point::new(50, 1)));
+        }
+
+        #[test]
+        fn test_contains_point_level_with_vertex() {
+            let pentagon = create_pentagon();
+
+            assert!(
+                pentagon.contains_point(&Point::new(50, 35)),
+                "Point between two vertices at the same height is inside"
+            );
+            assert!(
+                !pentagon.contains_point(&Point::new(0, 35)),
+                "Point level with a vertex but left of it is outside"
+            );
+            assert!(
+                !pentagon.contains_point(&Point::new(100, 35)),
+                "Point level with a vertex but right of it is outside"
+            );
+
+            // Just outside a slanted edge, where integer division used to round
+            // the crossing onto the wrong side
+            assert!(!create_triangle().contains_point(&Point::new(0, 1)));
+        }
+
         #[test]
         fn test_degenerate_cases() {
             test_all_polygons(|polygon| {
@@ -542,6 +1089,154 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_area() {
+            assert_eq!(create_square().area(), 10_000.0);
+            assert_eq!(create_triangle().area(), 4_350.0);
+        }
+
+        #[test]
+        fn test_rotated_about() {
+            let square = create_square();
+            let quarter = std::f32::consts::FRAC_PI_2;
+
+            // A quarter turn about the center maps each corner onto the next
+            let turned = square.rotated_about(&Point::new(50, 50), quarter);
+            assert_eq!(
+                turned.vertices_vec(),
+                vec![
+                    Point::new(100, 0),
+                    Point::new(100, 100),
+                    Point::new(0, 100),
+                    Point::new(0, 0),
+                ]
+            );
+            assert_eq!(square.rotated_about(&Point::ORIGIN, 0.0), square);
+        }
+
+        #[test]
+        fn test_bounding_box() {
+            let pentagon = create_pentagon();
+            let xs: Vec<_> = pentagon.vertices().map(|v| v.x).collect();
+            let ys: Vec<_> = pentagon.vertices().map(|v| v.y).collect();
+
+            let expected = (
+                Point::new(*xs.iter().min().unwrap(), *ys.iter().min().unwrap()),
+                Point::new(*xs.iter().max().unwrap(), *ys.iter().max().unwrap()),
+            );
+            assert_eq!(pentagon.bounding_box(), expected);
+            assert_eq!(expected, (Point::new(3, 0), Point::new(97, 90)));
+        }
+
+        #[test]
+        fn test_offset_edges() {
+            let square = create_square();
+            let center = square.center();
+            let offsets = square.offset_edges(5.0);
+
+            for (edge, offset) in square.outer_edges().iter().zip(&offsets) {
+                // Each edge of the square is axis-aligned, so compare the
+                // coordinate that's constant along it
+                let (before, after) = if edge.start.x == edge.end.x {
+                    (
+                        (edge.start.x - center.x).abs(),
+                        (offset.start.x - center.x).abs(),
+                    )
+                } else {
+                    (
+                        (edge.start.y - center.y).abs(),
+                        (offset.start.y - center.y).abs(),
+                    )
+                };
+                assert_eq!(after, before + 5, "{edge:?} offset to {offset:?}");
+            }
+
+            // Reversing the winding shouldn't flip the offset inward
+            let mut vertices = square.vertices_vec();
+            vertices.reverse();
+            let reversed = Polygon::new(vertices);
+            assert!(reversed.offset_edges(5.0).iter().all(|e| {
+                let mid = Point::new((e.start.x + e.end.x) / 2, (e.start.y + e.end.y) / 2);
+                !square.contains_point(&mid)
+            }));
+        }
+
+        #[test]
+        fn test_densified() {
+            let square = create_square();
+            let dense = square.densified(50.0);
+
+            // Each 100-unit side gains its midpoint
+            assert_eq!(
+                dense.vertices_vec(),
+                vec![
+                    Point::new(0, 0),
+                    Point::new(50, 0),
+                    Point::new(100, 0),
+                    Point::new(100, 50),
+                    Point::new(100, 100),
+                    Point::new(50, 100),
+                    Point::new(0, 100),
+                    Point::new(0, 50),
+                ]
+            );
+            assert_eq!(dense.area(), square.area());
+            assert_eq!(square.densified(200.0), square, "Short edges are kept");
+        }
+
+        #[test]
+        fn test_simplified() {
+            let square = create_square();
+            let dense = square.densified(25.0);
+            assert_eq!(dense.vertices().count(), 16);
+            assert_eq!(dense.simplified(0.5), square);
+
+            // A slight dent survives a small epsilon but not a large one
+            let dented = Polygon::new(vec![
+                Point::new(0, 0),
+                Point::new(50, 3),
+                Point::new(100, 0),
+                Point::new(100, 100),
+                Point::new(0, 100),
+            ]);
+            assert_eq!(dented.simplified(1.0), dented);
+            assert_eq!(dented.simplified(5.0).vertices().count(), 4);
+            assert!((dented.simplified(5.0).area() - dented.area()).abs() < 200.0);
+
+            assert_eq!(create_triangle().simplified(100.0), create_triangle());
+        }
+
+        #[test]
+        fn test_convex_hull() {
+            let points = [
+                Point::new(100, 100),
+                Point::new(0, 0),
+                Point::new(50, 50),
+                Point::new(0, 100),
+                Point::new(100, 0),
+            ];
+            assert_eq!(
+                Polygon::convex_hull(&points),
+                create_square(),
+                "Interior point should be dropped from the hull"
+            );
+        }
+
+        #[test]
+        fn test_convex_hull_collinear() {
+            let points = [
+                Point::new(20, 20),
+                Point::new(0, 0),
+                Point::new(10, 10),
+                Point::new(30, 30),
+            ];
+            assert_eq!(
+                Polygon::convex_hull(&points).vertices_vec(),
+                vec![Point::new(0, 0), Point::new(30, 30)],
+                "Collinear points should collapse to their extremes"
+            );
+        }
+
         #[test]
         fn test_edge_extraction() {
             test_all_polygons(|polygon| {
@@ -565,5 +1260,22 @@ mod tests {
                 }
             });
         }
+
+        #[test]
+        fn test_edges_indexed() {
+            let square = create_square();
+            let indexed: Vec<_> = square.edges_indexed().collect();
+
+            assert_eq!(indexed.len(), 4);
+            for (start, end, edge) in &indexed {
+                assert_eq!(edge.start, square.vertices_vec()[*start]);
+                assert_eq!(edge.end, square.vertices_vec()[*end]);
+            }
+            assert_eq!(
+                indexed[3],
+                (3, 0, Edge::new(Point::new(0, 100), Point::new(0, 0))),
+                "The last edge wraps around"
+            );
+        }
     }
 }
@@ -0,0 +1,61 @@
+//! Small seedable random number generation, so generated boards are
+//! reproducible from their seed alone without pulling in a dependency
+
+/// The SplitMix64 generator: fast, tiny, and good enough for board generation
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator whose sequence is fully determined by `seed`
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next value in the sequence
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `min..max`, which must not be empty
+    pub fn next_range(&mut self, min: i32, max: i32) -> i32 {
+        assert!(min < max, "empty range {min}..{max}");
+        let span = (i64::from(max) - i64::from(min)) as u64;
+        (i64::from(min) + (self.next_u64() % span) as i64) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SplitMix64::new(42);
+        let mut b = SplitMix64::new(42);
+        let mut c = SplitMix64::new(43);
+
+        let sequence: Vec<_> = (0..10).map(|_| a.next_u64()).collect();
+        assert_eq!(sequence, (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(sequence, (0..10).map(|_| c.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_next_range_stays_in_bounds() {
+        let mut rng = SplitMix64::new(7);
+
+        for _ in 0..1000 {
+            let value = rng.next_range(-5, 5);
+            assert!((-5..5).contains(&value), "{value} out of range");
+        }
+        assert_eq!(rng.next_range(3, 4), 3);
+        // Spans wider than i32 can hold don't overflow
+        let value = rng.next_range(i32::MIN, i32::MAX);
+        assert!(value < i32::MAX);
+    }
+}
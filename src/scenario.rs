@@ -0,0 +1,109 @@
+//! Everything needed to reproduce what the app shows, saved as one JSON file
+//! for bug reports and sharing.
+//!
+//! Documents look like `{"version": 1, "polygons": [...], "start": [x, y],
+//! "goal": [x, y], "variant": ..., "heuristic": ..., "show_solution": ...}`,
+//! a board file with the scenario's fields alongside, sharing its version.
+use serde::{Deserialize, Serialize};
+
+use crate::board::{document_version, BoardError, BoardV1};
+use crate::search::SearchVariant;
+use crate::{Board, Heuristic, Point};
+
+/// A board together with the endpoints, search settings and view options
+/// the app was showing it with
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub board: Board,
+    pub start: Point,
+    pub goal: Point,
+    pub variant: SearchVariant,
+    pub heuristic: Heuristic,
+    pub show_solution: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ScenarioV1 {
+    #[serde(flatten)]
+    board: BoardV1,
+    start: [i32; 2],
+    goal: [i32; 2],
+    variant: SearchVariant,
+    heuristic: Heuristic,
+    show_solution: bool,
+}
+
+impl Scenario {
+    /// Serializes the scenario to the current versioned JSON format
+    pub fn to_json(&self) -> String {
+        let document = ScenarioV1 {
+            board: BoardV1::new(&self.board),
+            start: self.start.into(),
+            goal: self.goal.into(),
+            variant: self.variant,
+            heuristic: self.heuristic,
+            show_solution: self.show_solution,
+        };
+
+        serde_json::to_string(&document).expect("Scenario should always serialize")
+    }
+
+    /// Loads a scenario from versioned JSON, checking the version before
+    /// reading the rest of the document
+    pub fn from_json(json: &str) -> Result<Self, BoardError> {
+        match document_version(json)? {
+            1 => {
+                let document: ScenarioV1 = serde_json::from_str(json)?;
+                Ok(Scenario {
+                    board: document.board.into_board(),
+                    start: document.start.into(),
+                    goal: document.goal.into(),
+                    variant: document.variant,
+                    heuristic: document.heuristic,
+                    show_solution: document.show_solution,
+                })
+            }
+            version => Err(BoardError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::sample_board;
+
+    #[test]
+    fn test_round_trip() {
+        let mut board = sample_board();
+        board.set_polygon_enabled(0, false);
+        let scenario = Scenario {
+            board,
+            start: Point::new(20, 30),
+            goal: Point::new(480, 470),
+            variant: SearchVariant::AStar,
+            heuristic: Heuristic::Blend(0.25),
+            show_solution: true,
+        };
+        let loaded = Scenario::from_json(&scenario.to_json()).unwrap();
+
+        assert_eq!(
+            loaded.board.polygons().collect::<Vec<_>>(),
+            scenario.board.polygons().collect::<Vec<_>>()
+        );
+        assert!(!loaded.board.is_polygon_enabled(0));
+        assert_eq!(loaded.start, scenario.start);
+        assert_eq!(loaded.goal, scenario.goal);
+        assert_eq!(loaded.variant, scenario.variant);
+        assert_eq!(loaded.heuristic, scenario.heuristic);
+        assert_eq!(loaded.show_solution, scenario.show_solution);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        assert_eq!(
+            Scenario::from_json(r#"{"version": 2}"#).unwrap_err(),
+            BoardError::UnsupportedVersion(2)
+        );
+    }
+}
@@ -1,13 +1,23 @@
 // search.rs
+mod direct;
+mod landmarks;
+mod open_set;
 mod simple;
 mod visibility;
 
+pub use direct::{shortest_path, shortest_path_cost};
+pub use landmarks::Landmarks;
 pub use simple::AStarPathfinder;
-pub use visibility::VisibilityGraphPathfinder;
+pub use visibility::{Sightline, VisibilityGraphPathfinder};
 
-use crate::{Board, Heuristic, Pathfinder, Point, SearchState};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+use crate::pathfinder::{hausdorff_distance, path_segment_diff, SegmentDiff};
+use crate::{Board, Heuristic, Pathfinder, Point, SearchState, SearchStatus};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SearchVariant {
     VisibilityGraph,
     AStar,
@@ -16,6 +26,22 @@ pub enum SearchVariant {
 impl SearchVariant {
     pub const ALL: &'static [SearchVariant] =
         &[SearchVariant::VisibilityGraph, SearchVariant::AStar];
+
+    /// Whether this variant makes use of `heuristic`, rather than estimating
+    /// like [`Heuristic::Euclidean`] in its place as the A* variant does for
+    /// [`Heuristic::Landmark`], since only the visibility graph search
+    /// precomputes landmarks
+    pub fn supports(self, heuristic: Heuristic) -> bool {
+        self == SearchVariant::VisibilityGraph || !matches!(heuristic, Heuristic::Landmark { .. })
+    }
+
+    /// The variant to compare this one against
+    pub fn other(self) -> Self {
+        match self {
+            SearchVariant::VisibilityGraph => SearchVariant::AStar,
+            SearchVariant::AStar => SearchVariant::VisibilityGraph,
+        }
+    }
 }
 
 impl std::fmt::Display for SearchVariant {
@@ -27,6 +53,60 @@ impl std::fmt::Display for SearchVariant {
     }
 }
 
+/// How the path found with another [`Heuristic`] compares to the current one
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeuristicDiff {
+    /// The heuristic the search was rerun with
+    pub heuristic: Heuristic,
+    /// Path cost with `heuristic` minus the current path cost
+    pub cost_delta: i32,
+    /// Hausdorff distance between the two paths, 0.0 if they coincide
+    pub hausdorff: f64,
+    /// The two paths' segments split by [`path_segment_diff`], the current
+    /// path first
+    pub segments: SegmentDiff,
+}
+
+impl std::fmt::Display for HeuristicDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.hausdorff == 0.0 && self.cost_delta == 0 {
+            write!(f, "{}: same path", self.heuristic)
+        } else {
+            write!(
+                f,
+                "{}: cost {:+}, Hausdorff {:.1}",
+                self.heuristic, self.cost_delta, self.hausdorff
+            )
+        }
+    }
+}
+
+/// Settings a [`Search`] is built with, so it runs once with all of them in
+/// place rather than again for each one applied afterwards
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchOptions {
+    /// Heuristic weight, see [`Search::set_weight`]
+    pub weight: f32,
+    /// Search the reduced visibility graph, see
+    /// [`VisibilityGraphPathfinder::with_taut_pruning`]. Ignored by variants
+    /// that don't build one.
+    pub reduced_graph: bool,
+    /// Step edge by edge, see
+    /// [`VisibilityGraphPathfinder::with_edge_granularity`]. Ignored by
+    /// other variants.
+    pub edge_granularity: bool,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            weight: 1.0,
+            reduced_graph: false,
+            edge_granularity: false,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum Search {
     Visibility(VisibilityGraphPathfinder),
@@ -73,6 +153,131 @@ impl Search {
             }
         }
     }
+
+    /// Like [`Search::new_for_variant`], but searching with `options` in
+    /// place from the start
+    pub fn new_with_options(
+        board: Board,
+        start: Point,
+        goal: Point,
+        heuristic: Heuristic,
+        variant: SearchVariant,
+        options: SearchOptions,
+    ) -> Self {
+        match variant {
+            SearchVariant::VisibilityGraph => Self::Visibility(
+                VisibilityGraphPathfinder::new_with_options(board, start, goal, heuristic, options),
+            ),
+            SearchVariant::AStar => Self::AStar(AStarPathfinder::new_with_weight(
+                board,
+                start,
+                goal,
+                heuristic,
+                options.weight,
+            )),
+        }
+    }
+
+    /// How far playback is through the search, from 0.0 at the first step to
+    /// 1.0 at the last
+    pub fn progress(&self) -> f32 {
+        match self.total_steps() {
+            0 => 1.0,
+            total => self.current_step() as f32 / total as f32,
+        }
+    }
+
+    /// Jumps to the step `progress` of the way through the search, clamped
+    /// to [0, 1], so searches with different step counts can be played back
+    /// in lockstep
+    pub fn jump_to_progress(&mut self, progress: f32) -> bool {
+        let total = self.total_steps() as f32;
+        self.jump_to((progress.clamp(0.0, 1.0) * total).round() as usize)
+    }
+
+    /// Returns the ratio of this search's path cost to `optimal_cost`, e.g.
+    /// from [`shortest_path_cost`], or `None` if no path was found
+    pub fn cost_ratio(&self, optimal_cost: i32) -> Option<f64> {
+        let cost = self.optimal_path_score()?;
+        if optimal_cost == 0 {
+            return Some(1.0);
+        }
+        Some(cost as f64 / optimal_cost as f64)
+    }
+
+    /// Reruns a copy of this search with the other [`Heuristic`] and compares
+    /// the resulting path to the current one, both by cost and segment by
+    /// segment, or returns `None` if either search finds no path
+    pub fn diff_heuristics(&self) -> Option<HeuristicDiff> {
+        let other = self.with_other_heuristic();
+        let (path, cost) = self.get_optimal_path()?;
+        let (other_path, other_cost) = other.get_optimal_path()?;
+
+        Some(HeuristicDiff {
+            heuristic: other.get_heuristic(),
+            cost_delta: other_cost - cost,
+            hausdorff: hausdorff_distance(path, other_path),
+            segments: path_segment_diff(path, other_path),
+        })
+    }
+
+    /// A copy of this search rerun with the heuristic to compare against
+    fn with_other_heuristic(&self) -> Self {
+        let heuristic = match self.get_heuristic() {
+            Heuristic::Euclidean => Heuristic::Manhattan,
+            Heuristic::Manhattan | Heuristic::Blend(_) | Heuristic::Landmark { .. } => {
+                Heuristic::Euclidean
+            }
+        };
+        let mut other = self.clone();
+        other.change_heuristic(heuristic);
+        other
+    }
+
+    /// Returns the sightlines from `vertex` for the visibility graph variant,
+    /// or `None` for variants that don't build one
+    pub fn sightlines(&self, vertex: Point) -> Option<Vec<Sightline>> {
+        match self {
+            Search::Visibility(p) => Some(p.sightlines(vertex)),
+            Search::AStar(_) => None,
+        }
+    }
+
+    /// Returns the visibility graph for the visibility graph variant, or
+    /// `None` for variants that don't build one
+    pub fn visibility_graph(&self) -> Option<&HashMap<Point, HashSet<Point>>> {
+        match self {
+            Search::Visibility(p) => Some(p.visibility_graph()),
+            Search::AStar(_) => None,
+        }
+    }
+
+    /// Returns a copy of the search that continues from the current step's
+    /// frontier rather than the start, e.g. to explore what a different
+    /// heuristic would do from here
+    pub fn fork_from_current_step(&self) -> Self {
+        match self {
+            Search::Visibility(p) => Search::Visibility(p.fork_from_current_step()),
+            Search::AStar(p) => Search::AStar(p.fork_from_current_step()),
+        }
+    }
+
+    /// Reruns the search as weighted A* with the given heuristic weight
+    pub fn set_weight(&mut self, weight: f32) {
+        match self {
+            Search::Visibility(p) => p.set_weight(weight),
+            Search::AStar(p) => p.set_weight(weight),
+        }
+    }
+
+    /// Reruns the search in place for a new board, endpoints and heuristic,
+    /// keeping whatever precomputed data the variant can reuse
+    pub fn rebuild(&mut self, board: Board, start: Point, goal: Point, heuristic: Heuristic) {
+        match self {
+            Search::Visibility(p) => p.rebuild(board, start, goal, heuristic),
+            Search::AStar(p) => p.rebuild(board, start, goal, heuristic),
+        }
+    }
 }
 
 // Delegate all trait methods to the contained implementation
@@ -125,6 +330,13 @@ impl Pathfinder for Search {
         }
     }
 
+    fn into_optimal_path(self) -> Option<(Vec<Point>, i32)> {
+        match self {
+            Self::Visibility(p) => p.into_optimal_path(),
+            Self::AStar(p) => p.into_optimal_path(),
+        }
+    }
+
     fn current_step(&self) -> usize {
         match self {
             Self::Visibility(p) => p.current_step(),
@@ -160,6 +372,27 @@ impl Pathfinder for Search {
         }
     }
 
+    fn goal_discovery_step(&self) -> Option<usize> {
+        match self {
+            Self::Visibility(p) => p.goal_discovery_step(),
+            Self::AStar(p) => p.goal_discovery_step(),
+        }
+    }
+
+    fn reopening_steps(&self) -> &[usize] {
+        match self {
+            Self::Visibility(p) => p.reopening_steps(),
+            Self::AStar(p) => p.reopening_steps(),
+        }
+    }
+
+    fn step_timings(&self) -> &[Duration] {
+        match self {
+            Self::Visibility(p) => p.step_timings(),
+            Self::AStar(p) => p.step_timings(),
+        }
+    }
+
     fn reset(&mut self) {
         match self {
             Self::Visibility(p) => p.reset(),
@@ -173,4 +406,132 @@ impl Pathfinder for Search {
             Self::AStar(p) => p.change_heuristic(heuristic),
         }
     }
+
+    fn edge_cost(&self, from: &Point, to: &Point) -> i32 {
+        match self {
+            Self::Visibility(p) => p.edge_cost(from, to),
+            Self::AStar(p) => p.edge_cost(from, to),
+        }
+    }
+
+    fn status(&self) -> SearchStatus {
+        match self {
+            Self::Visibility(p) => p.status(),
+            Self::AStar(p) => p.status(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::sample_board;
+
+    #[test]
+    fn test_new_with_options() {
+        let board = sample_board();
+        let (start, goal) = board.default_endpoints();
+        let search = |variant, options| {
+            Search::new_with_options(
+                board.clone(),
+                start,
+                goal,
+                Heuristic::Euclidean,
+                variant,
+                options,
+            )
+        };
+
+        let full = search(SearchVariant::VisibilityGraph, SearchOptions::default());
+        let options = SearchOptions {
+            reduced_graph: true,
+            edge_granularity: true,
+            ..SearchOptions::default()
+        };
+        let Search::Visibility(reduced) = search(SearchVariant::VisibilityGraph, options) else {
+            panic!("Expected a visibility graph search");
+        };
+        assert!(reduced.optimal_path_score().is_some());
+        assert_eq!(reduced.optimal_path_score(), full.optimal_path_score());
+        assert!(reduced.history().iter().any(|s| s.active_edge.is_some()));
+
+        let astar = search(SearchVariant::AStar, options);
+        assert_eq!(astar.variant(), SearchVariant::AStar);
+    }
+
+    #[test]
+    fn test_jump_to_progress() {
+        // Different heuristics so the two searches take different step counts
+        let (start, goal) = (Point::new(0, 700), Point::new(480, 480));
+        let mut searches = [
+            (SearchVariant::VisibilityGraph, Heuristic::Euclidean),
+            (SearchVariant::AStar, Heuristic::Manhattan),
+        ]
+        .map(|(variant, heuristic)| {
+            Search::new_for_variant(Board::default(), start, goal, heuristic, variant)
+        });
+        assert_ne!(searches[0].total_steps(), searches[1].total_steps());
+
+        for search in &mut searches {
+            assert!(search.jump_to_progress(1.0));
+            assert_eq!(search.current_step(), search.total_steps());
+            assert_eq!(search.progress(), 1.0);
+
+            search.jump_to_progress(0.5);
+            let half = (search.total_steps() as f32 / 2.0).round() as usize;
+            assert_eq!(search.current_step(), half);
+
+            search.jump_to_progress(-1.0);
+            assert_eq!(search.current_step(), 0);
+        }
+    }
+
+    #[test]
+    fn test_fork_from_current_step() {
+        let board = sample_board();
+        let (start, goal) = board.default_endpoints();
+        for variant in [SearchVariant::VisibilityGraph, SearchVariant::AStar] {
+            let fresh =
+                Search::new_for_variant(board.clone(), start, goal, Heuristic::Euclidean, variant);
+
+            let fork = fresh.fork_from_current_step();
+            assert_eq!(fork.total_steps(), fresh.total_steps(), "{variant}");
+            assert_eq!(
+                fork.get_optimal_path(),
+                fresh.get_optimal_path(),
+                "{variant}"
+            );
+
+            // Resuming partway only expands what's left, still optimally
+            let mut partway = fresh.clone();
+            partway.jump_to(3);
+            let fork = partway.fork_from_current_step();
+            assert_eq!(fork.total_steps(), fresh.total_steps() - 3, "{variant}");
+            assert_eq!(fork.optimal_path_score(), fresh.optimal_path_score());
+            assert_eq!(fork.history()[0].closed, partway.get_state().closed);
+        }
+    }
+
+    #[test]
+    fn test_diff_heuristics() {
+        // Manhattan overestimates diagonal distances here, steering the search
+        // onto a longer route around the obstacles
+        let search = Search::new_for_variant(
+            Board::default(),
+            Point::new(0, 700),
+            Point::new(480, 480),
+            Heuristic::Euclidean,
+            SearchVariant::VisibilityGraph,
+        );
+
+        let diff = search.diff_heuristics().unwrap();
+        assert_eq!(diff.heuristic, Heuristic::Manhattan);
+        assert!(diff.cost_delta > 0);
+        assert!(
+            diff.hausdorff > 0.0,
+            "The heuristics should pick different routes"
+        );
+        let (_, only_current, only_other) = &diff.segments;
+        assert!(!only_current.is_empty() && !only_other.is_empty());
+    }
 }
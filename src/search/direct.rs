@@ -0,0 +1,140 @@
+//! Searches that return just the answer, without recording history for
+//! playback.
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::{Board, Heuristic, Point};
+
+/// Finds the optimal path and its cost by building the visibility graph and
+/// running A* over it, without recording any history.
+///
+/// This is the simplest entry point when only the answer matters. Costs match
+/// [`crate::Pathfinder::get_optimal_path`].
+pub fn shortest_path(
+    board: &Board,
+    start: Point,
+    goal: Point,
+    heuristic: Heuristic,
+) -> Option<(Vec<Point>, i32)> {
+    search(board, start, goal, Some(heuristic))
+}
+
+/// Computes the true optimal path cost with a plain Dijkstra search over the
+/// visibility graph, as a reference for variants that may return suboptimal
+/// paths.
+///
+/// Edges cost the same as [`crate::Pathfinder::distance`], so an optimal
+/// variant's cost matches this exactly.
+pub fn shortest_path_cost(board: &Board, start: Point, goal: Point) -> Option<i32> {
+    search(board, start, goal, None).map(|(_, cost)| cost)
+}
+
+/// A* over the visibility graph, or Dijkstra when there's no heuristic
+fn search(
+    board: &Board,
+    start: Point,
+    goal: Point,
+    heuristic: Option<Heuristic>,
+) -> Option<(Vec<Point>, i32)> {
+    let mut nodes: Vec<Point> = board.vertices().into_iter().collect();
+    nodes.extend([start, goal]);
+    nodes.sort_by_key(|p| (p.x, p.y));
+    nodes.dedup();
+
+    let index = |point: Point| nodes.iter().position(|&p| p == point);
+    let (start_index, goal_index) = (index(start)?, index(goal)?);
+
+    let graph: Vec<Vec<usize>> = nodes
+        .iter()
+        .map(|from| {
+            (0..nodes.len())
+                .filter(|&j| board.vertices_visible(from, &nodes[j]))
+                .collect()
+        })
+        .collect();
+    let h = |i: usize| heuristic.map_or(0, |h| h.distance(&nodes[i], &goal));
+
+    let mut g_scores = vec![i32::MAX; nodes.len()];
+    let mut came_from = vec![None; nodes.len()];
+    g_scores[start_index] = 0;
+
+    // Ordered by f-score, then by push order so ties are stable
+    let mut pushed = 0;
+    let mut open = BinaryHeap::from([Reverse((h(start_index), pushed, start_index))]);
+
+    while let Some(Reverse((_, _, current))) = open.pop() {
+        if current == goal_index {
+            let mut path = vec![nodes[current]];
+            let mut vertex = current;
+            while let Some(parent) = came_from[vertex] {
+                path.push(nodes[parent]);
+                vertex = parent;
+            }
+            path.reverse();
+            return Some((path, g_scores[goal_index]));
+        }
+
+        for &next in &graph[current] {
+            let g =
+                g_scores[current] + Heuristic::Euclidean.distance(&nodes[current], &nodes[next]);
+            if g < g_scores[next] {
+                g_scores[next] = g;
+                came_from[next] = Some(current);
+                pushed += 1;
+                open.push(Reverse((g + h(next), pushed, next)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::sample_board;
+    use crate::search::{Search, SearchVariant, VisibilityGraphPathfinder};
+    use crate::{Pathfinder, Polygon};
+
+    fn create_test_board() -> Board {
+        Board::new(vec![Polygon::new(vec![
+            (40, 40).into(),
+            (40, 60).into(),
+            (60, 60).into(),
+            (60, 40).into(),
+        ])])
+    }
+
+    #[test]
+    fn test_shortest_path_matches_pathfinder() {
+        let board = create_test_board();
+        let start = Point::new(0, 10);
+        let goal = Point::new(100, 100);
+        let search =
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+
+        assert_eq!(
+            shortest_path(&board, start, goal, Heuristic::Euclidean).as_ref(),
+            search.get_optimal_path()
+        );
+    }
+
+    #[test]
+    fn test_optimal_variants_match_reference() {
+        let board = sample_board();
+        let start = Point::new(115, 655);
+        let goal = Point::new(380, 560);
+        let optimal = shortest_path_cost(&board, start, goal).unwrap();
+
+        for &variant in SearchVariant::ALL {
+            let search =
+                Search::new_for_variant(board.clone(), start, goal, Heuristic::Euclidean, variant);
+
+            assert_eq!(
+                search.cost_ratio(optimal),
+                Some(1.0),
+                "{variant} should find the optimal path"
+            );
+        }
+    }
+}
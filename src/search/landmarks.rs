@@ -0,0 +1,198 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::rng::SplitMix64;
+use crate::{Heuristic, LandmarkStrategy, Point};
+
+/// Precomputed shortest path costs from a few landmark vertices to every
+/// vertex of a graph, for the ALT (A*, landmarks, triangle inequality)
+/// heuristic.
+///
+/// By the triangle inequality, `|d(L, goal) - d(L, v)|` never exceeds the
+/// cost from `v` to the goal for any landmark `L`, so the largest such bound
+/// is admissible. Each extra landmark can only raise that maximum, trading
+/// one more Dijkstra pass when built for tighter estimates and fewer
+/// expansions during the search.
+#[derive(Debug, Clone, Default)]
+pub struct Landmarks {
+    landmarks: Vec<Point>,
+    /// Cost from each landmark to every vertex reachable from it
+    costs: Vec<HashMap<Point, i32>>,
+}
+
+impl Landmarks {
+    /// Picks up to `count` landmarks among `candidates` with `strategy` and
+    /// computes their costs over `graph`, where edges cost their rounded
+    /// down Euclidean length
+    pub fn new(
+        graph: &HashMap<Point, HashSet<Point>>,
+        candidates: &[Point],
+        count: usize,
+        strategy: LandmarkStrategy,
+    ) -> Self {
+        let mut landmarks = Self::default();
+        let count = count.min(candidates.len());
+
+        match strategy {
+            LandmarkStrategy::Farthest => {
+                if let Some(&first) = candidates.first().filter(|_| count > 0) {
+                    landmarks.add(graph, first);
+                }
+            }
+            LandmarkStrategy::Random(seed) => {
+                let mut rng = SplitMix64::new(seed);
+                let mut remaining = candidates.to_vec();
+                while landmarks.landmarks.len() < count {
+                    let index = (rng.next_u64() % remaining.len() as u64) as usize;
+                    landmarks.add(graph, remaining.swap_remove(index));
+                }
+            }
+            LandmarkStrategy::Corners => {
+                let xs = candidates.iter().map(|point| point.x);
+                let ys = candidates.iter().map(|point| point.y);
+                let (min_x, max_x) = (xs.clone().min(), xs.max());
+                let (min_y, max_y) = (ys.clone().min(), ys.max());
+                if let (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) =
+                    (min_x, max_x, min_y, max_y)
+                {
+                    let corners = [
+                        Point::new(min_x, min_y),
+                        Point::new(max_x, max_y),
+                        Point::new(max_x, min_y),
+                        Point::new(min_x, max_y),
+                    ];
+                    for corner in corners.iter().take(count) {
+                        let closest = candidates.iter().min_by_key(|candidate| {
+                            Heuristic::Euclidean.distance(*candidate, corner)
+                        });
+                        if let Some(&closest) = closest {
+                            if !landmarks.landmarks.contains(&closest) {
+                                landmarks.add(graph, closest);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Farthest-point selection, which also tops up the other strategies:
+        // each new landmark is the candidate farthest from all landmarks so
+        // far, preferring any the landmarks can't reach at all
+        while landmarks.landmarks.len() < count {
+            let farthest = candidates
+                .iter()
+                .filter(|candidate| !landmarks.landmarks.contains(candidate))
+                .max_by_key(|candidate| {
+                    landmarks
+                        .costs
+                        .iter()
+                        .map(|costs| costs.get(*candidate).copied().unwrap_or(i32::MAX))
+                        .min()
+                        .unwrap_or(0)
+                });
+            match farthest {
+                Some(&farthest) => landmarks.add(graph, farthest),
+                None => break,
+            }
+        }
+
+        landmarks
+    }
+
+    /// Lower bound on the cost from `from` to `to`, or 0 when no landmark
+    /// reaches both of them
+    pub fn estimate(&self, from: &Point, to: &Point) -> i32 {
+        self.costs
+            .iter()
+            .filter_map(|costs| Some((costs.get(from)? - costs.get(to)?).abs()))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn add(&mut self, graph: &HashMap<Point, HashSet<Point>>, landmark: Point) {
+        self.landmarks.push(landmark);
+        self.costs.push(dijkstra(graph, landmark));
+    }
+}
+
+/// Cost from `source` to every vertex reachable from it in `graph`
+fn dijkstra(graph: &HashMap<Point, HashSet<Point>>, source: Point) -> HashMap<Point, i32> {
+    let mut costs = HashMap::from([(source, 0)]);
+    // Points aren't ordered, so the heap holds their coordinates
+    let mut open = BinaryHeap::from([Reverse((0, source.x, source.y))]);
+
+    while let Some(Reverse((cost, x, y))) = open.pop() {
+        let vertex = Point::new(x, y);
+        if costs.get(&vertex).is_some_and(|&best| cost > best) {
+            continue;
+        }
+        for neighbor in graph.get(&vertex).into_iter().flatten() {
+            let tentative = cost + Heuristic::Euclidean.distance(&vertex, neighbor);
+            if costs.get(neighbor).is_none_or(|&best| tentative < best) {
+                costs.insert(*neighbor, tentative);
+                open.push(Reverse((tentative, neighbor.x, neighbor.y)));
+            }
+        }
+    }
+
+    costs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A ring of vertices around a square, each connected to its neighbors
+    fn create_ring() -> (HashMap<Point, HashSet<Point>>, Vec<Point>) {
+        let points: Vec<Point> = [(0, 0), (50, 0), (100, 0), (100, 100), (50, 100), (0, 100)]
+            .into_iter()
+            .map(Point::from)
+            .collect();
+        let mut graph: HashMap<Point, HashSet<Point>> = HashMap::new();
+        for (i, &point) in points.iter().enumerate() {
+            let next = points[(i + 1) % points.len()];
+            graph.entry(point).or_default().insert(next);
+            graph.entry(next).or_default().insert(point);
+        }
+        (graph, points)
+    }
+
+    #[test]
+    fn test_estimate_is_admissible() {
+        let (graph, points) = create_ring();
+        for strategy in [
+            LandmarkStrategy::Farthest,
+            LandmarkStrategy::Random(3),
+            LandmarkStrategy::Corners,
+        ] {
+            for count in [1, 4] {
+                let landmarks = Landmarks::new(&graph, &points, count, strategy);
+                assert_eq!(landmarks.landmarks.len(), count);
+
+                for goal in &points {
+                    let exact = dijkstra(&graph, *goal);
+                    for point in &points {
+                        assert!(
+                            landmarks.estimate(point, goal) <= exact[point],
+                            "{strategy:?} with {count} landmarks overestimates"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_farthest_spreads_landmarks() {
+        let (graph, points) = create_ring();
+        let landmarks = Landmarks::new(&graph, &points, 2, LandmarkStrategy::Farthest);
+        assert_eq!(
+            landmarks.landmarks,
+            [Point::new(0, 0), Point::new(100, 100)]
+        );
+        assert_eq!(
+            landmarks.estimate(&Point::new(0, 0), &Point::new(100, 100)),
+            200
+        );
+    }
+}
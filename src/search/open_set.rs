@@ -0,0 +1,198 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use crate::OpenSetKind;
+
+/// A node that can be queued in an [`OpenSet`]
+pub trait OpenNode: Ord {
+    /// What the node is queued for, e.g. its vertex. An open set holds at
+    /// most one live node per key.
+    type Key: Copy + Eq + Hash;
+
+    fn key(&self) -> Self::Key;
+
+    /// Number telling apart nodes pushed for the same key, unique per push
+    fn sequence(&self) -> usize;
+}
+
+/// Priority queue of nodes waiting to be expanded, handing out the smallest
+/// node by its [`Ord`] first
+pub trait OpenSet<N: OpenNode> {
+    /// Adds a node to the queue
+    fn push(&mut self, node: N);
+
+    /// Removes and returns the smallest node, if any
+    fn pop_min(&mut self) -> Option<N>;
+
+    /// Replaces the queued node with the same key as `node`, e.g. when a
+    /// cheaper path to an open vertex is found
+    fn decrease_key(&mut self, node: N);
+
+    /// Removes all nodes
+    fn clear(&mut self);
+}
+
+/// [`OpenSet`] backed by a binary heap with lazy deletion: a decreased key
+/// is pushed again, and the superseded copy is skipped when it's popped, so
+/// every operation stays O(log n)
+#[derive(Debug, Clone)]
+pub struct HeapOpenSet<N, K> {
+    heap: BinaryHeap<Reverse<N>>,
+    /// Sequence of the live node for each queued key
+    live: HashMap<K, usize>,
+}
+
+impl<N: Ord, K> Default for HeapOpenSet<N, K> {
+    fn default() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            live: HashMap::new(),
+        }
+    }
+}
+
+impl<N: OpenNode<Key = K>, K: Copy + Eq + Hash> OpenSet<N> for HeapOpenSet<N, K> {
+    fn push(&mut self, node: N) {
+        self.live.insert(node.key(), node.sequence());
+        self.heap.push(Reverse(node));
+    }
+
+    fn pop_min(&mut self) -> Option<N> {
+        while let Some(Reverse(node)) = self.heap.pop() {
+            if self.live.get(&node.key()) == Some(&node.sequence()) {
+                self.live.remove(&node.key());
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    fn decrease_key(&mut self, node: N) {
+        self.push(node);
+    }
+
+    fn clear(&mut self) {
+        self.heap.clear();
+        self.live.clear();
+    }
+}
+
+/// [`OpenSet`] backed by a `Vec` kept sorted from largest to smallest, so
+/// popping is O(1) but every push shifts elements in O(n)
+#[derive(Debug, Clone)]
+pub struct SortedVecOpenSet<N>(Vec<N>);
+
+impl<N> Default for SortedVecOpenSet<N> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<N: OpenNode> OpenSet<N> for SortedVecOpenSet<N> {
+    fn push(&mut self, node: N) {
+        let index = self.0.partition_point(|queued| *queued > node);
+        self.0.insert(index, node);
+    }
+
+    fn pop_min(&mut self) -> Option<N> {
+        self.0.pop()
+    }
+
+    /// Removes the superseded node eagerly, so this is O(n)
+    fn decrease_key(&mut self, node: N) {
+        self.0.retain(|queued| queued.key() != node.key());
+        self.push(node);
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// The [`OpenSet`] implementation picked by an [`OpenSetKind`]
+#[derive(Debug, Clone)]
+pub(crate) enum OpenNodes<N, K> {
+    BinaryHeap(HeapOpenSet<N, K>),
+    SortedVec(SortedVecOpenSet<N>),
+}
+
+impl<N: Ord, K> OpenNodes<N, K> {
+    pub(crate) fn new(kind: OpenSetKind) -> Self {
+        match kind {
+            OpenSetKind::BinaryHeap => Self::BinaryHeap(HeapOpenSet::default()),
+            OpenSetKind::SortedVec => Self::SortedVec(SortedVecOpenSet::default()),
+        }
+    }
+}
+
+impl<N: OpenNode<Key = K>, K: Copy + Eq + Hash> OpenSet<N> for OpenNodes<N, K> {
+    fn push(&mut self, node: N) {
+        match self {
+            Self::BinaryHeap(open) => open.push(node),
+            Self::SortedVec(open) => open.push(node),
+        }
+    }
+
+    fn pop_min(&mut self) -> Option<N> {
+        match self {
+            Self::BinaryHeap(open) => open.pop_min(),
+            Self::SortedVec(open) => open.pop_min(),
+        }
+    }
+
+    fn decrease_key(&mut self, node: N) {
+        match self {
+            Self::BinaryHeap(open) => open.decrease_key(node),
+            Self::SortedVec(open) => open.decrease_key(node),
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            Self::BinaryHeap(open) => open.clear(),
+            Self::SortedVec(open) => open.clear(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A priority for key `.1`, pushed as the `.2`th node
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    struct Node(u32, char, usize);
+
+    impl OpenNode for Node {
+        type Key = char;
+
+        fn key(&self) -> char {
+            self.1
+        }
+
+        fn sequence(&self) -> usize {
+            self.2
+        }
+    }
+
+    fn drain(open: &mut impl OpenSet<Node>) -> Vec<Node> {
+        std::iter::from_fn(|| open.pop_min()).collect()
+    }
+
+    #[test]
+    fn test_decrease_key_supersedes_node() {
+        for kind in [OpenSetKind::BinaryHeap, OpenSetKind::SortedVec] {
+            let mut open = OpenNodes::new(kind);
+            open.push(Node(5, 'a', 0));
+            open.push(Node(3, 'b', 1));
+            open.decrease_key(Node(1, 'a', 2));
+
+            assert_eq!(
+                drain(&mut open),
+                [Node(1, 'a', 2), Node(3, 'b', 1)],
+                "{kind:?} should hand out each key once, with its latest priority"
+            );
+        }
+    }
+}
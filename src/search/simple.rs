@@ -1,110 +1,274 @@
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::time::{Duration, Instant};
 
-use crate::{Board, Heuristic, Pathfinder, Point, SearchState};
+use super::open_set::{OpenNode, OpenNodes, OpenSet};
+use crate::pathfinder::{goal_discovery_step, reopening_steps};
+use crate::{
+    Board, Coordinate, Heuristic, MovementModel, OpenSetKind, Pathfinder, Point, SearchState,
+    SearchStatus, TieBreak,
+};
 
 /// A* pathfinding implementation following the textbook approach:
 /// - No visibility graph preprocessing
 /// - Explores points dynamically
 /// - Maintains OPEN and CLOSED lists explicitly
 /// - Reopens CLOSED nodes when better paths are found
+///
+/// Generic over the [`Coordinate`] type, so it runs on integer or floating
+/// point boards alike.
 #[derive(Clone)]
-pub struct AStarPathfinder {
-    board: Board,
-    start: Point,
-    goal: Point,
+pub struct AStarPathfinder<T = i32> {
+    board: Board<T>,
+    start: Point<T>,
+    goal: Point<T>,
     heuristic: Heuristic,
-    state: SearchState,
-    history: Vec<SearchState>,
+    state: SearchState<T>,
+    /// State a forked search resumes from instead of the start
+    initial_state: Option<SearchState<T>>,
+    history: Vec<SearchState<T>>,
+    /// Time spent on each expansion's successors
+    step_timings: Vec<Duration>,
     current_step: usize,
-    optimal_path: Option<(Vec<Point>, i32)>,
+    optimal_path: Option<(Vec<Point<T>>, T)>,
+    /// First history frame with the goal in the open set
+    goal_discovery_step: Option<usize>,
+    /// History frames where a closed vertex got a cheaper path
+    reopening_steps: Vec<usize>,
+    /// Constant cost added to every edge, favoring paths with fewer vertices
+    hop_penalty: T,
+    tie_break: TieBreak,
+    movement: MovementModel,
+    /// Factor inflating the heuristic; above 1.0 trades optimality for speed
+    weight: f32,
+    /// Farthest a single move may reach, if limited
+    search_radius: Option<f64>,
+    /// Waypoints closer than this to an earlier one are merged into it
+    vertex_epsilon: Option<T>,
+    /// Points the search may move to, as given by the [`MovementModel`]
+    waypoints: Vec<Point<T>>,
+    /// How the search ended, reported once playback reaches the last step
+    outcome: SearchStatus,
     // Store these separately since they're not part of visualization state
-    open_nodes: BinaryHeap<SearchNode>,
+    open_nodes: OpenNodes<SearchNode<T>, Point<T>>,
+    /// Number of nodes pushed so far, used for stable tie-breaking
+    pushed: usize,
 }
 
-#[derive(Clone, Eq, PartialEq)]
-struct SearchNode {
-    vertex: Point,
-    g_score: i32,
-    f_score: i32,
+#[derive(Clone, PartialEq)]
+struct SearchNode<T> {
+    vertex: Point<T>,
+    g_score: T,
+    f_score: T,
+    /// Order in which the node was pushed, used for stable tie-breaking
+    sequence: usize,
+    tie_break: TieBreak,
 }
 
-impl Ord for SearchNode {
+impl<T: Coordinate> Eq for SearchNode<T> {}
+
+/// Nodes to expand first compare smallest, as [`OpenSet::pop_min`] expects
+impl<T: Coordinate> Ord for SearchNode<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.f_score.cmp(&self.f_score)
+        let by = |a: T, b: T| a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+
+        by(self.f_score, other.f_score)
+            .then_with(|| match self.tie_break {
+                TieBreak::FifoStable => Ordering::Equal,
+                TieBreak::HigherG => by(other.g_score, self.g_score),
+                TieBreak::LowerH => by(self.f_score - self.g_score, other.f_score - other.g_score),
+            })
+            .then_with(|| self.sequence.cmp(&other.sequence))
+    }
+}
+
+impl<T: Coordinate> OpenNode for SearchNode<T> {
+    type Key = Point<T>;
+
+    fn key(&self) -> Point<T> {
+        self.vertex
+    }
+
+    fn sequence(&self) -> usize {
+        self.sequence
     }
 }
 
-impl PartialOrd for SearchNode {
+impl<T: Coordinate> PartialOrd for SearchNode<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl AStarPathfinder {
-    pub fn history(&self) -> &[SearchState] {
+impl<T: Coordinate> AStarPathfinder<T> {
+    pub fn history(&self) -> &[SearchState<T>] {
         &self.history
     }
-}
 
-impl Pathfinder for AStarPathfinder {
-    fn new(board: Board, start: Point, goal: Point, heuristic: Heuristic) -> Self {
-        let mut search = Self {
-            board,
-            start,
-            goal,
-            heuristic,
-            optimal_path: None,
-            state: SearchState {
-                open: HashSet::from([start]),
-                closed: HashSet::new(),
-                current_paths: HashMap::from([(start, vec![start])]),
-                best_path: None,
-                considered_edges: HashSet::new(),
-                next_vertex: Some(start),
-                g_scores: HashMap::from([(start, 0)]),
-                came_from: HashMap::new(),
-            },
-            history: Vec::new(),
-            current_step: 0,
-            open_nodes: BinaryHeap::new(),
-        };
+    /// Creates a search with a constant cost added to every edge, running it
+    /// once with the penalty already in place
+    pub fn new_with_hop_penalty(
+        board: Board<T>,
+        start: Point<T>,
+        goal: Point<T>,
+        heuristic: Heuristic,
+        hop_penalty: T,
+    ) -> Self {
+        let mut search = Self::unsolved(board, start, goal, heuristic);
+        search.hop_penalty = hop_penalty;
+        search.recompute();
+        search
+    }
 
-        // Initialize start node
-        search.open_nodes.push(SearchNode {
-            vertex: start,
-            g_score: 0,
-            f_score: heuristic.distance(&start, &goal),
-        });
+    /// Creates a weighted A* search, see [`Self::set_weight`], running it
+    /// once with the weight already in place
+    pub fn new_with_weight(
+        board: Board<T>,
+        start: Point<T>,
+        goal: Point<T>,
+        heuristic: Heuristic,
+        weight: f32,
+    ) -> Self {
+        let mut search = Self::unsolved(board, start, goal, heuristic);
+        search.weight = weight;
+        search.recompute();
+        search
+    }
 
-        // Compute solution and history
-        search.compute_optimal_path();
-        search.history.push(search.state.clone());
-        search.reset();
+    /// Rebuilds the search with the given ordering for tied f-scores
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self.recompute();
+        self
+    }
+
+    /// Rebuilds the search keeping open nodes in the given data structure.
+    /// Paths and expansion order are the same for every [`OpenSetKind`].
+    pub fn with_open_set(mut self, open_set: OpenSetKind) -> Self {
+        self.open_nodes = OpenNodes::new(open_set);
+        self.recompute();
+        self
+    }
+
+    /// Rebuilds the search treating waypoints within `eps` of each other as a
+    /// single vertex, so near-duplicate float vertices, e.g. from smoothing,
+    /// don't bloat the search. The start and goal are kept exactly.
+    pub fn with_vertex_epsilon(mut self, eps: T) -> Self {
+        self.vertex_epsilon = Some(eps);
+        self.recompute();
+        self
+    }
+
+    /// Rebuilds the search as a windowed search that only considers
+    /// successors within `radius` of the current vertex.
+    ///
+    /// This speeds up search on large boards, but gives up completeness: if
+    /// reaching the goal needs a move longer than `radius`, no path is found
+    /// even though one exists. Such a search ends as
+    /// [`SearchStatus::Incomplete`] rather than [`SearchStatus::NoPath`].
+    pub fn with_search_radius(mut self, radius: f64) -> Self {
+        self.search_radius = Some(radius);
+        self.recompute();
+        self
+    }
+
+    /// Rebuilds the search with the given restriction on move directions
+    pub fn with_movement(mut self, movement: MovementModel) -> Self {
+        self.movement = movement;
+        self.recompute();
+        self
+    }
 
+    /// Reruns the search as weighted A*, inflating the heuristic by `weight`.
+    /// A weight of 1.0 is plain A*; higher weights expand fewer nodes but may
+    /// return paths up to `weight` times longer than optimal.
+    pub fn set_weight(&mut self, weight: f32) {
+        self.weight = weight;
+        self.recompute();
+    }
+
+    /// Returns a copy of the search that resumes from the current step's OPEN
+    /// and CLOSED lists instead of starting over, so that changing e.g. the
+    /// heuristic or weight afterwards continues from this frontier.
+    ///
+    /// The fork's history begins at the current step, with the node that
+    /// step moved to CLOSED back on OPEN, since its successors are generated
+    /// only after the step is recorded.
+    pub fn fork_from_current_step(&self) -> Self {
+        let mut state = self.state.clone();
+        let previous = self
+            .current_step
+            .checked_sub(1)
+            .map(|step| &self.history[step].closed);
+        let pending: Vec<Point<T>> = state
+            .closed
+            .iter()
+            .filter(|vertex| previous.is_none_or(|closed| !closed.contains(vertex)))
+            .copied()
+            .collect();
+        for vertex in pending {
+            state.closed.remove(&vertex);
+            state.open.insert(vertex);
+        }
+
+        let mut fork = self.clone();
+        fork.initial_state = Some(state);
+        fork.recompute();
+        fork
+    }
+
+    /// Reruns the search for a new board, endpoints and heuristic, reusing
+    /// the existing buffers
+    pub fn rebuild(
+        &mut self,
+        board: Board<T>,
+        start: Point<T>,
+        goal: Point<T>,
+        heuristic: Heuristic,
+    ) {
+        if start != self.start || board != self.board {
+            // A forked frontier only makes sense from the start it grew from
+            self.initial_state = None;
+        }
+        self.board = board;
+        self.start = start;
+        self.goal = goal;
+        self.heuristic = heuristic;
+        self.recompute();
+    }
+}
+
+impl<T: Coordinate> Pathfinder<T> for AStarPathfinder<T> {
+    fn new(board: Board<T>, start: Point<T>, goal: Point<T>, heuristic: Heuristic) -> Self {
+        let mut search = Self::unsolved(board, start, goal, heuristic);
+        // Compute solution and history
+        search.recompute();
         search
     }
 
-    fn get_board(&self) -> &Board {
+    fn get_board(&self) -> &Board<T> {
         &self.board
     }
-    fn get_state(&self) -> &SearchState {
+    fn get_state(&self) -> &SearchState<T> {
         &self.state
     }
-    fn get_start(&self) -> Point {
+    fn get_start(&self) -> Point<T> {
         self.start
     }
-    fn get_goal(&self) -> Point {
+    fn get_goal(&self) -> Point<T> {
         self.goal
     }
     fn get_heuristic(&self) -> Heuristic {
         self.heuristic
     }
 
-    fn get_optimal_path(&self) -> Option<&(Vec<Point>, i32)> {
+    fn get_optimal_path(&self) -> Option<&(Vec<Point<T>>, T)> {
         self.optimal_path.as_ref()
     }
 
+    fn into_optimal_path(self) -> Option<(Vec<Point<T>>, T)> {
+        self.optimal_path
+    }
+
     fn total_steps(&self) -> usize {
         self.history.len() - 1
     }
@@ -140,6 +304,18 @@ impl Pathfinder for AStarPathfinder {
         true
     }
 
+    fn goal_discovery_step(&self) -> Option<usize> {
+        self.goal_discovery_step
+    }
+
+    fn reopening_steps(&self) -> &[usize] {
+        &self.reopening_steps
+    }
+
+    fn step_timings(&self) -> &[Duration] {
+        &self.step_timings
+    }
+
     fn reset(&mut self) {
         self.current_step = 0;
         self.state = self.history[0].clone();
@@ -147,27 +323,128 @@ impl Pathfinder for AStarPathfinder {
 
     fn change_heuristic(&mut self, heuristic: Heuristic) {
         self.heuristic = heuristic;
-        self.reset();
-        self.compute_optimal_path();
+        self.recompute();
+    }
+
+    fn status(&self) -> SearchStatus {
+        if self.is_finished() {
+            self.outcome
+        } else {
+            SearchStatus::Running
+        }
+    }
+
+    fn edge_cost(&self, from: &Point<T>, to: &Point<T>) -> T {
+        Self::distance(from, to) + self.hop_penalty
     }
 }
 
-impl AStarPathfinder {
+impl<T: Coordinate> AStarPathfinder<T> {
+    /// A search with default settings that hasn't run yet
+    fn unsolved(board: Board<T>, start: Point<T>, goal: Point<T>, heuristic: Heuristic) -> Self {
+        Self {
+            board,
+            start,
+            goal,
+            heuristic,
+            hop_penalty: T::zero(),
+            tie_break: TieBreak::default(),
+            movement: MovementModel::default(),
+            weight: 1.0,
+            search_radius: None,
+            vertex_epsilon: None,
+            waypoints: Vec::new(),
+            outcome: SearchStatus::Incomplete,
+            optimal_path: None,
+            goal_discovery_step: None,
+            reopening_steps: Vec::new(),
+            state: SearchState::new(start),
+            initial_state: None,
+            history: Vec::new(),
+            step_timings: Vec::new(),
+            current_step: 0,
+            open_nodes: OpenNodes::new(OpenSetKind::default()),
+            pushed: 0,
+        }
+    }
+
+    /// Discards any previous results and runs the search from scratch
+    fn recompute(&mut self) {
+        self.state = self
+            .initial_state
+            .clone()
+            .unwrap_or_else(|| SearchState::new(self.start));
+        self.optimal_path = None;
+        self.outcome = SearchStatus::Incomplete;
+        self.open_nodes.clear();
+        self.pushed = 0;
+        self.step_timings.clear();
+
+        // Already there: a single frame with the trivial path, no search needed
+        if self.start == self.goal {
+            self.state.best_path = Some(vec![self.start]);
+            self.optimal_path = Some((vec![self.start], T::zero()));
+            self.outcome = SearchStatus::Found;
+            self.history = vec![self.state.clone()];
+        } else {
+            self.waypoints = self.movement.waypoints(&self.board, self.start, self.goal);
+            if let Some(eps) = self.vertex_epsilon {
+                self.merge_close_waypoints(eps);
+            }
+            // The search records its own final frame, found or not
+            self.compute_optimal_path();
+        }
+
+        self.goal_discovery_step = goal_discovery_step(&self.history, &self.goal);
+        self.reopening_steps = reopening_steps(&self.history);
+        self.reset();
+    }
+
+    /// Drops every waypoint within `eps` of the start, the goal or an earlier
+    /// waypoint
+    fn merge_close_waypoints(&mut self, eps: T) {
+        let mut kept = vec![self.start, self.goal];
+        for waypoint in std::mem::take(&mut self.waypoints) {
+            if !kept.iter().any(|p| p.approx_eq(&waypoint, eps)) {
+                kept.push(waypoint);
+            }
+        }
+
+        // The start is never a waypoint, while the goal always is
+        kept.remove(0);
+        self.waypoints = kept;
+    }
+
     fn compute_optimal_path(&mut self) {
         self.history.clear();
 
-        // Step 1: Initialize OPEN with start node
-        let h_start = self.heuristic.distance(&self.start, &self.goal);
-        self.open_nodes.push(SearchNode {
-            vertex: self.start,
-            g_score: 0,
-            f_score: h_start,
-        });
-        self.state.g_scores.insert(self.start, 0);
-        self.state.open.insert(self.start);
+        // Step 1: Initialize OPEN with start node, or the forked OPEN list
+        let frontier = match &self.initial_state {
+            Some(state) => state.open_sorted(),
+            None => vec![self.start],
+        };
+        self.state.open.clear();
+        for vertex in frontier {
+            let g = self
+                .state
+                .g_scores
+                .get(&vertex)
+                .copied()
+                .unwrap_or(T::zero());
+            let h = self
+                .heuristic
+                .weighted_distance(&vertex, &self.goal, self.weight);
+            self.push_node(vertex, g, g + h);
+            self.state.g_scores.insert(vertex, g);
+            self.state.open.insert(vertex);
+        }
+
+        // Whether the radius left out any move, so running out of OPEN nodes
+        // doesn't prove there is no path
+        let mut cut_off = false;
 
         // Step 2: Main loop
-        while let Some(best_node) = self.open_nodes.pop() {
+        while let Some(best_node) = self.open_nodes.pop_min() {
             let best_vertex = best_node.vertex;
 
             // Check if we've reached the goal
@@ -175,24 +452,32 @@ impl AStarPathfinder {
                 let path = self.reconstruct_path(&best_vertex);
                 self.optimal_path = Some((path.clone(), best_node.g_score));
                 self.state.best_path = Some(path);
+                self.outcome = SearchStatus::Found;
                 self.history.push(self.state.clone());
                 return;
             }
 
             // Move BESTNODE from OPEN to CLOSED
             self.state.open.remove(&best_vertex);
-            self.state.closed.insert(best_vertex);
+            self.state.close(best_vertex);
 
             // Save state for visualization
             self.history.push(self.state.clone());
+            let expansion = Instant::now();
 
             // Generate successors
+            cut_off |= self
+                .waypoints
+                .iter()
+                .any(|v| !self.within_radius(&best_vertex, v));
             for successor in self.get_successors(&best_vertex) {
                 // Calculate tentative g score (g in the textbook)
-                let successor_g = best_node.g_score + Self::distance(&best_vertex, &successor);
+                let successor_g = best_node.g_score + self.edge_cost(&best_vertex, &successor);
 
                 // Calculate h' value for successor
-                let successor_h = self.heuristic.distance(&successor, &self.goal);
+                let successor_h =
+                    self.heuristic
+                        .weighted_distance(&successor, &self.goal, self.weight);
                 let successor_f = successor_g + successor_h;
 
                 // Check if successor is on OPEN (step 2c in textbook)
@@ -216,20 +501,26 @@ impl AStarPathfinder {
                 }
                 // Successor is new (step 2e in textbook)
                 else {
-                    self.state.open.insert(successor);
                     self.update_node(&successor, &best_vertex, successor_g, successor_f);
+                    self.state.open.insert(successor);
                 }
 
                 // Record edge for visualization
-                self.state.considered_edges.insert((best_vertex, successor));
+                self.state.consider(best_vertex, successor);
             }
+            self.step_timings.push(expansion.elapsed());
         }
 
         // No path found - record final state
+        self.outcome = if cut_off {
+            SearchStatus::Incomplete
+        } else {
+            SearchStatus::NoPath
+        };
         self.history.push(self.state.clone());
     }
 
-    fn update_node(&mut self, node: &Point, parent: &Point, g_score: i32, f_score: i32) {
+    fn update_node(&mut self, node: &Point<T>, parent: &Point<T>, g_score: T, f_score: T) {
         self.state.came_from.insert(*node, *parent);
         self.state.g_scores.insert(*node, g_score);
 
@@ -237,40 +528,51 @@ impl AStarPathfinder {
         new_path.push(*node);
         self.state.current_paths.insert(*node, new_path);
 
-        self.open_nodes.push(SearchNode {
-            vertex: *node,
-            g_score,
-            f_score,
-        });
+        self.push_node(*node, g_score, f_score);
     }
 
-    fn get_successors(&self, vertex: &Point) -> Vec<Point> {
-        let mut successors = Vec::new();
+    /// Queues a node, replacing its earlier entry if it's already open
+    fn push_node(&mut self, vertex: Point<T>, g_score: T, f_score: T) {
+        let node = SearchNode {
+            vertex,
+            g_score,
+            f_score,
+            sequence: self.pushed,
+            tie_break: self.tie_break,
+        };
+        self.pushed += 1;
 
-        // Add visible polygon vertices as successors
-        for polygon in self.board.polygons() {
-            for v in polygon.vertices() {
-                if self.is_valid_move(vertex, v) {
-                    successors.push(*v);
-                }
-            }
+        if self.state.open.contains(&vertex) {
+            self.open_nodes.decrease_key(node);
+        } else {
+            self.open_nodes.push(node);
         }
+    }
 
-        // Always consider goal if we can see it
-        if self.is_valid_move(vertex, &self.goal) {
-            successors.push(self.goal);
-        }
+    fn get_successors(&self, vertex: &Point<T>) -> Vec<Point<T>> {
+        // Waypoints are the polygon vertices followed by the goal, or the
+        // free grid points when movement is orthogonal
+        self.waypoints
+            .iter()
+            .filter(|v| self.within_radius(vertex, v))
+            .filter(|v| self.is_valid_move(vertex, v))
+            .copied()
+            .collect()
+    }
 
-        successors
+    /// Whether a move is short enough for the search radius, if there is one
+    fn within_radius(&self, from: &Point<T>, to: &Point<T>) -> bool {
+        self.search_radius
+            .is_none_or(|radius| Self::path_length_f64(&[*from, *to]) <= radius)
     }
 
-    fn is_valid_move(&self, from: &Point, to: &Point) -> bool {
-        if from == to {
+    fn is_valid_move(&self, from: &Point<T>, to: &Point<T>) -> bool {
+        if from == to || !self.movement.allows(from, to) {
             return false;
         }
 
         // Check against each polygon
-        for polygon in self.board.polygons() {
+        for polygon in self.board.enabled_polygons() {
             if polygon.intersects_segment(from, to) {
                 return false;
             }
@@ -283,7 +585,9 @@ impl AStarPathfinder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::board::sample_board;
     use crate::Polygon;
+    use std::collections::HashSet;
 
     fn create_test_board() -> Board {
         let polygons = vec![Polygon::new(vec![
@@ -295,6 +599,31 @@ mod tests {
         Board::new(polygons)
     }
 
+    /// Three thin walls, the middle one raised above the outer two, that a
+    /// path from (20, 20) across to the right has to weave between
+    fn three_slit_board() -> Board {
+        Board::new(vec![
+            Polygon::new(vec![
+                (30, 0).into(),
+                (30, 40).into(),
+                (35, 40).into(),
+                (35, 0).into(),
+            ]),
+            Polygon::new(vec![
+                (50, 20).into(),
+                (50, 60).into(),
+                (55, 60).into(),
+                (55, 20).into(),
+            ]),
+            Polygon::new(vec![
+                (70, 0).into(),
+                (70, 40).into(),
+                (75, 40).into(),
+                (75, 0).into(),
+            ]),
+        ])
+    }
+
     #[test]
     fn test_path_found() {
         let board = create_test_board();
@@ -322,16 +651,11 @@ mod tests {
         assert_eq!(*path.last().unwrap(), goal);
 
         // Check no segments intersect obstacles
-        for window in path.windows(2) {
-            let from = window[0];
-            let to = window[1];
-            assert!(
-                search.is_valid_move(&from, &to),
-                "Path segment from {:?} to {:?} intersects obstacle",
-                from,
-                to
-            );
-        }
+        assert!(
+            board.path_is_valid(path),
+            "Path {:?} intersects obstacle",
+            path
+        );
     }
 
     #[test]
@@ -369,28 +693,7 @@ mod tests {
 
     #[test]
     fn test_path_optimality() {
-        // Use same board setup as reopening test
-        let board = Board::new(vec![
-            Polygon::new(vec![
-                (30, 0).into(),
-                (30, 40).into(),
-                (35, 40).into(),
-                (35, 0).into(),
-            ]),
-            Polygon::new(vec![
-                (50, 20).into(),
-                (50, 60).into(),
-                (55, 60).into(),
-                (55, 20).into(),
-            ]),
-            Polygon::new(vec![
-                (70, 0).into(),
-                (70, 40).into(),
-                (75, 40).into(),
-                (75, 0).into(),
-            ]),
-        ]);
-
+        let board = three_slit_board();
         let start = Point::new(20, 20);
         let goal = Point::new(80, 20);
 
@@ -419,4 +722,348 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_reopening_steps() {
+        // The inadmissible Manhattan heuristic closes some vertices too early,
+        // but only once the goal is off the start's row. With it level, as in
+        // test_path_optimality, nothing is ever reopened.
+        let search = AStarPathfinder::new(
+            three_slit_board(),
+            Point::new(20, 20),
+            Point::new(80, 30),
+            Heuristic::Manhattan,
+        );
+
+        assert!(!search.reopening_steps().is_empty());
+        for &step in search.reopening_steps() {
+            let (before, after) = (&search.history[step - 1], &search.history[step]);
+            assert!(before
+                .closed
+                .iter()
+                .any(|v| after.g_scores[v] < before.g_scores[v]));
+        }
+    }
+
+    #[test]
+    fn test_float_coordinates() {
+        let board: Board<f64> = Board::new(vec![Polygon::new(vec![
+            Point::new(40.0, 40.0),
+            Point::new(40.0, 60.0),
+            Point::new(60.0, 60.0),
+            Point::new(60.0, 40.0),
+        ])]);
+
+        let start = Point::new(0.0, 50.0);
+        let goal = Point::new(100.0, 50.0);
+        let search = AStarPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+
+        let (path, cost) = search.get_optimal_path().unwrap();
+        assert!(board.path_is_valid(path), "Path {:?} is invalid", path);
+
+        // Around a corner: 2 * hypot(40, 10) + 20
+        let expected = 2.0 * 40.0_f64.hypot(10.0) + 20.0;
+        assert!(
+            (cost - expected).abs() < 1e-9,
+            "Expected cost {expected}, got {cost}"
+        );
+    }
+
+    #[test]
+    fn test_vertex_epsilon() {
+        let (a, b) = (Point::new(10.0, 10.0), Point::new(10.0 + 1e-6, 10.0 - 1e-6));
+        assert!(a.approx_eq(&b, 1e-5));
+        assert!(!a.approx_eq(&b, 1e-7));
+        assert_ne!(a, b);
+
+        // Two squares whose touching corners differ only by rounding error
+        let board: Board<f64> = Board::new(vec![
+            Polygon::new(vec![
+                Point::new(0.0, 0.0),
+                Point::new(0.0, 10.0),
+                a,
+                Point::new(10.0, 0.0),
+            ]),
+            Polygon::new(vec![
+                b,
+                Point::new(10.0, 20.0),
+                Point::new(20.0, 20.0),
+                Point::new(20.0, 10.0),
+            ]),
+        ]);
+        let (start, goal) = (Point::new(-5.0, 30.0), Point::new(30.0, 5.0));
+        let search = || AStarPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+
+        let exact = search();
+        let merged = search().with_vertex_epsilon(1e-3);
+        assert_eq!(merged.waypoints.len(), exact.waypoints.len() - 1);
+        assert_eq!(
+            merged
+                .waypoints
+                .iter()
+                .filter(|p| p.approx_eq(&a, 1e-3))
+                .count(),
+            1
+        );
+        assert!(merged.get_optimal_path().is_some());
+    }
+
+    #[test]
+    fn test_hop_penalty_prefers_fewer_vertices() {
+        // Going over the top takes three hops and is slightly shorter than
+        // going around the bottom tip, which takes only two
+        let board = Board::new(vec![Polygon::new(vec![
+            (40, 50).into(),
+            (60, 50).into(),
+            (60, 0).into(),
+            (50, -58).into(),
+            (40, 0).into(),
+        ])]);
+        let (start, goal) = (Point::new(0, 0), Point::new(100, 0));
+
+        let plain = AStarPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let penalized =
+            AStarPathfinder::new_with_hop_penalty(board, start, goal, Heuristic::Euclidean, 10);
+
+        let (plain_path, _) = plain.get_optimal_path().unwrap();
+        let (penalized_path, penalized_cost) = penalized.get_optimal_path().unwrap();
+        assert_eq!(
+            plain_path.len(),
+            4,
+            "Unpenalized path should go over the top"
+        );
+        assert_eq!(
+            penalized_path.len(),
+            3,
+            "Penalized path should go around the bottom tip"
+        );
+
+        // The cost includes one penalty per hop
+        let length: i32 = penalized_path
+            .windows(2)
+            .map(|w| AStarPathfinder::distance(&w[0], &w[1]))
+            .sum();
+        assert_eq!(*penalized_cost, length + 20);
+    }
+
+    #[test]
+    fn test_orthogonal_movement() {
+        let board = create_test_board();
+        let start = Point::new(0, 0);
+        let goal = Point::new(100, 100);
+        let search = AStarPathfinder::new(board.clone(), start, goal, Heuristic::Manhattan)
+            .with_movement(MovementModel::Orthogonal);
+
+        let (path, cost) = search.get_optimal_path().unwrap();
+        assert!(board.path_is_valid(path), "Path {:?} is invalid", path);
+        for window in path.windows(2) {
+            assert!(
+                window[0].x == window[1].x || window[0].y == window[1].y,
+                "Segment {:?} -> {:?} is not axis-aligned",
+                window[0],
+                window[1]
+            );
+        }
+        assert_eq!(
+            *cost, 200,
+            "Orthogonal cost should equal the Manhattan distance"
+        );
+    }
+
+    #[test]
+    fn test_weighted_search() {
+        let board = crate::board::sample_board();
+        let start = Point::new(115, 655);
+        let goal = Point::new(380, 560);
+
+        let plain = AStarPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let mut weighted = plain.clone();
+
+        weighted.set_weight(1.0);
+        assert_eq!(
+            weighted.get_optimal_path(),
+            plain.get_optimal_path(),
+            "A weight of 1.0 should reproduce the optimal path"
+        );
+
+        weighted.set_weight(3.0);
+        let optimal = plain.optimal_path_score().unwrap();
+        let cost = weighted.optimal_path_score().unwrap();
+        assert!(
+            optimal <= cost && cost as f32 <= 3.0 * optimal as f32,
+            "Weighted cost {cost} should be within 3x of {optimal}"
+        );
+        assert!(weighted.history().len() <= plain.history().len());
+
+        let built = AStarPathfinder::new_with_weight(board, start, goal, Heuristic::Euclidean, 3.0);
+        assert_eq!(built.optimal_path_score(), weighted.optimal_path_score());
+    }
+
+    #[test]
+    fn test_found_path() {
+        // The goal sits inside the obstacle, so the search exhausts
+        let board = create_test_board();
+        let (start, goal) = (Point::new(0, 0), Point::new(50, 50));
+        let mut search = AStarPathfinder::new(board, start, goal, Heuristic::Euclidean);
+        assert!(!search.found_path());
+
+        search.jump_to(search.total_steps());
+        assert!(search.is_finished());
+        assert!(!search.found_path());
+
+        let mut search = AStarPathfinder::new(
+            create_test_board(),
+            start,
+            Point::new(100, 100),
+            Heuristic::Euclidean,
+        );
+        search.jump_to(search.total_steps());
+        assert!(search.is_finished());
+        assert!(search.found_path());
+
+        // The goal is in free space, but walled in on every side
+        let wall = |x1, y1, x2, y2| {
+            Polygon::new(vec![
+                (x1, y1).into(),
+                (x1, y2).into(),
+                (x2, y2).into(),
+                (x2, y1).into(),
+            ])
+        };
+        let ring = Board::new(vec![
+            wall(0, 0, 10, 100),
+            wall(90, 0, 100, 100),
+            wall(0, 0, 100, 10),
+            wall(0, 90, 100, 100),
+        ]);
+        let (start, goal) = (Point::new(-50, 50), Point::new(50, 50));
+        assert!(!ring.contains_point(&goal));
+        let mut search = AStarPathfinder::new(ring, start, goal, Heuristic::Euclidean);
+        search.jump_to(search.total_steps());
+        assert!(search.is_finished());
+        assert!(!search.found_path());
+    }
+
+    #[test]
+    fn test_search_radius() {
+        let (start, goal) = (Point::new(0, 0), Point::new(100, 100));
+        let search =
+            || AStarPathfinder::new(create_test_board(), start, goal, Heuristic::Euclidean);
+
+        let unrestricted = search();
+        let generous = search().with_search_radius(500.0);
+        assert!(unrestricted.get_optimal_path().is_some());
+        assert_eq!(generous.get_optimal_path(), unrestricted.get_optimal_path());
+
+        // Every move from the start to an obstacle corner is longer than this
+        let tiny = search().with_search_radius(10.0);
+        assert_eq!(tiny.get_optimal_path(), None);
+        assert_eq!(tiny.outcome, SearchStatus::Incomplete);
+    }
+
+    #[test]
+    fn test_status() {
+        let search = |goal| {
+            let mut search = AStarPathfinder::new(
+                create_test_board(),
+                Point::new(0, 0),
+                goal,
+                Heuristic::Euclidean,
+            );
+            assert_eq!(search.status(), SearchStatus::Running);
+            search.jump_to(search.total_steps());
+            search.status()
+        };
+
+        assert_eq!(search(Point::new(100, 100)), SearchStatus::Found);
+        // The goal sits inside the obstacle, so it can never be reached
+        assert_eq!(search(Point::new(50, 50)), SearchStatus::NoPath);
+    }
+
+    #[test]
+    fn test_open_set_kinds_agree() {
+        let search = |open_set| {
+            AStarPathfinder::new(
+                sample_board(),
+                Point::new(0, 450),
+                Point::new(480, 740),
+                Heuristic::Euclidean,
+            )
+            .with_open_set(open_set)
+        };
+        let heap = search(OpenSetKind::BinaryHeap);
+        let sorted = search(OpenSetKind::SortedVec);
+
+        assert!(heap.get_optimal_path().is_some());
+        assert_eq!(heap.get_optimal_path(), sorted.get_optimal_path());
+        assert_eq!(
+            heap.total_steps(),
+            sorted.total_steps(),
+            "Same expansion count"
+        );
+    }
+
+    #[test]
+    fn test_start_equals_goal() {
+        let point = Point::new(10, 10);
+        let search = AStarPathfinder::new(create_test_board(), point, point, Heuristic::Euclidean);
+
+        assert_eq!(search.get_optimal_path(), Some(&(vec![point], 0)));
+        assert_eq!(search.total_steps(), 0);
+        assert_eq!(search.status(), SearchStatus::Found);
+    }
+
+    #[test]
+    fn test_step_timings() {
+        // Every expansion frame is timed; only the final one is not, whether
+        // the search reaches the goal or gives up
+        for goal in [Point::new(100, 100), Point::new(50, 50)] {
+            let search = AStarPathfinder::new(
+                create_test_board(),
+                Point::new(0, 0),
+                goal,
+                Heuristic::Euclidean,
+            );
+            assert!(search.history().len() > 1);
+            assert_eq!(search.step_timings().len(), search.history().len() - 1);
+        }
+    }
+
+    #[test]
+    fn test_higher_g_tie_break() {
+        // A lattice of small squares whose bottom row lies along the line
+        // from start to goal, so every vertex on that line ties on f-score
+        let mut polygons = Vec::new();
+        for i in 0..4 {
+            for j in 0..4 {
+                let (x, y) = (20 + 20 * i, 20 + 20 * j);
+                polygons.push(Polygon::new(vec![
+                    (x, y).into(),
+                    (x, y + 5).into(),
+                    (x + 5, y + 5).into(),
+                    (x + 5, y).into(),
+                ]));
+            }
+        }
+        let board = Board::new(polygons);
+
+        let start = Point::new(0, 20);
+        let goal = Point::new(100, 20);
+
+        let fifo = AStarPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let higher_g = AStarPathfinder::new(board, start, goal, Heuristic::Euclidean)
+            .with_tie_break(TieBreak::HigherG);
+
+        assert!(
+            higher_g.history().len() <= fifo.history().len(),
+            "HigherG expanded {} nodes, FifoStable expanded {}",
+            higher_g.history().len(),
+            fifo.history().len()
+        );
+        assert_eq!(
+            higher_g.optimal_path_score(),
+            fifo.optimal_path_score(),
+            "Tie-breaking should not change the optimal cost"
+        );
+    }
 }
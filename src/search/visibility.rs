@@ -1,7 +1,27 @@
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
-
-use crate::{Board, Heuristic, Pathfinder, Point, SearchState};
+use std::time::{Duration, Instant};
+
+use super::{Landmarks, SearchOptions};
+use crate::pathfinder::{goal_discovery_step, reopening_steps, smooth_path};
+use crate::{
+    Board, Edge, Heuristic, MovementModel, Pathfinder, Point, SearchState, SearchStatus, TieBreak,
+};
+
+/// Slack allowed for rounding when checking a smoothed path is no longer than
+/// the raw one
+const SMOOTHING_EPSILON: f64 = 1e-6;
+
+/// The line of sight from one vertex to another, as checked when building the
+/// visibility graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sightline {
+    /// The vertex being looked at
+    pub to: Point,
+    /// Where the line first meets the obstacle blocking it, or `None` if `to`
+    /// is visible
+    pub blocked_at: Option<Point>,
+}
 
 #[derive(Debug, Clone)]
 /// A* pathfinding implementation using pre-computed visibility graph
@@ -11,10 +31,46 @@ pub struct VisibilityGraphPathfinder {
     goal: Point,
     heuristic: Heuristic,
     visibility_graph: HashMap<Point, HashSet<Point>>,
+    /// Landmark costs over the visibility graph for [`Heuristic::Landmark`],
+    /// along with the heuristic they were picked for, kept until the graph
+    /// changes
+    landmarks: Option<(Heuristic, Landmarks)>,
+    /// Number of times landmarks were picked and their costs computed
+    landmark_builds: usize,
+    /// Number of times the visibility graph was built from scratch
+    graph_builds: usize,
     state: SearchState,
+    /// State a forked search resumes from instead of the start
+    initial_state: Option<SearchState>,
     history: Vec<SearchState>,
+    /// Time spent on each expansion's successors
+    step_timings: Vec<Duration>,
     current_step: usize,
     optimal_path: Option<(Vec<Point>, i32)>,
+    /// First history frame with the goal in the open set
+    goal_discovery_step: Option<usize>,
+    /// History frames where a closed vertex got a cheaper path
+    reopening_steps: Vec<usize>,
+    /// Constant cost added to every edge, favoring paths with fewer vertices
+    hop_penalty: i32,
+    /// How strongly edges close to obstacles are penalized
+    clearance_weight: f32,
+    /// [`Board::gap_clearance`] of each graph edge, both ways round, measured
+    /// with the graph while `clearance_weight` is set
+    clearances: HashMap<(Point, Point), f64>,
+    tie_break: TieBreak,
+    movement: MovementModel,
+    /// Factor inflating the heuristic; above 1.0 trades optimality for speed
+    weight: f32,
+    /// Drop visibility edges that bend into an obstacle at one of their ends
+    prune_non_taut: bool,
+    /// Round the optimal path's corners where that stays collision-free
+    smoothing: bool,
+    /// Record a history frame for every relaxed edge, not just every
+    /// expansion
+    edge_granularity: bool,
+    /// How the search ended, reported once playback reaches the last step
+    outcome: SearchStatus,
 }
 
 #[derive(Clone, Eq, PartialEq)]
@@ -22,11 +78,24 @@ struct SearchNode {
     vertex: Point,
     g_score: i32,
     f_score: i32,
+    /// Order in which the node was pushed, used for stable tie-breaking
+    sequence: usize,
+    tie_break: TieBreak,
 }
 
 impl Ord for SearchNode {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.f_score.cmp(&self.f_score)
+        other
+            .f_score
+            .cmp(&self.f_score)
+            .then_with(|| match self.tie_break {
+                TieBreak::FifoStable => Ordering::Equal,
+                TieBreak::HigherG => self.g_score.cmp(&other.g_score),
+                TieBreak::LowerH => {
+                    (other.f_score - other.g_score).cmp(&(self.f_score - self.g_score))
+                }
+            })
+            .then_with(|| other.sequence.cmp(&self.sequence))
     }
 }
 
@@ -40,37 +109,218 @@ impl VisibilityGraphPathfinder {
     pub fn history(&self) -> &[SearchState] {
         &self.history
     }
+
+    /// The visibility graph the search runs on, mapping each vertex to the
+    /// vertices it can see
+    pub fn visibility_graph(&self) -> &HashMap<Point, HashSet<Point>> {
+        &self.visibility_graph
+    }
+
+    /// Creates a search with a constant cost added to every edge, running it
+    /// once with the penalty already in place
+    pub fn new_with_hop_penalty(
+        board: Board,
+        start: Point,
+        goal: Point,
+        heuristic: Heuristic,
+        hop_penalty: i32,
+    ) -> Self {
+        let mut search = Self::unsolved(board, start, goal, heuristic);
+        search.hop_penalty = hop_penalty;
+        search.solve();
+        search
+    }
+
+    /// Creates a weighted A* search, see [`Self::set_weight`], running it
+    /// once with the weight already in place
+    pub fn new_with_weight(
+        board: Board,
+        start: Point,
+        goal: Point,
+        heuristic: Heuristic,
+        weight: f32,
+    ) -> Self {
+        let mut search = Self::unsolved(board, start, goal, heuristic);
+        search.weight = weight;
+        search.solve();
+        search
+    }
+
+    /// Creates a search with the given [`SearchOptions`], building the graph
+    /// and running the search once with all of them in place
+    pub fn new_with_options(
+        board: Board,
+        start: Point,
+        goal: Point,
+        heuristic: Heuristic,
+        options: SearchOptions,
+    ) -> Self {
+        let mut search = Self::unsolved(board, start, goal, heuristic);
+        search.weight = options.weight;
+        search.prune_non_taut = options.reduced_graph;
+        search.edge_granularity = options.edge_granularity;
+        search.solve();
+        search
+    }
+
+    /// Rebuilds the search with every edge's cost increased by its length
+    /// times `weight / (1 + c)`, where `c` is its [`Board::gap_clearance`],
+    /// trading path length for room between obstacles. A weight of 0.0, the
+    /// default, searches for the shortest path.
+    ///
+    /// Penalties only ever add cost, so the heuristic stays admissible and the
+    /// search still finds the cheapest path under the penalized cost. That is
+    /// a best-effort compromise, neither the shortest nor the roomiest path
+    /// in general, and the reported score includes the penalties rather than
+    /// being the path's length.
+    pub fn with_clearance_weight(mut self, weight: f32) -> Self {
+        self.clearance_weight = weight.max(0.0);
+        self.measure_clearances();
+        self.recompute();
+        self
+    }
+
+    /// Rebuilds the search with the given ordering for tied f-scores
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self.recompute();
+        self
+    }
+
+    /// Rebuilds the visibility graph and search with the given restriction on
+    /// move directions
+    pub fn with_movement(mut self, movement: MovementModel) -> Self {
+        self.movement = movement;
+        self.rebuild_visibility_graph();
+        self.recompute();
+        self
+    }
+
+    /// Rebuilds the visibility graph without edges that can never lie on a
+    /// shortest path, leaving the optimal cost unchanged.
+    ///
+    /// A shortest path only turns at a polygon vertex when it wraps tightly
+    /// around the corner, so any edge whose line would have to bend into the
+    /// obstacle at either end is dropped, along with vertices left without
+    /// edges. What remains is the reduced visibility graph, whose edges
+    /// between obstacles run along their common tangents. Only applies to
+    /// [`MovementModel::AnyAngle`].
+    pub fn with_taut_pruning(mut self, prune_non_taut: bool) -> Self {
+        self.prune_non_taut = prune_non_taut;
+        self.rebuild_visibility_graph();
+        self.recompute();
+        self
+    }
+
+    /// Rebuilds the search rounding the optimal path's corners for a cleaner
+    /// look, see [`smooth_path`]. The raw path is kept if the smoothed one
+    /// would cross an obstacle or be longer, and the search history is
+    /// unaffected either way. The cost is repriced along the smoothed path.
+    ///
+    /// Orthogonal paths are never smoothed, since cutting a corner would
+    /// leave the grid directions. A plain shortest path is taut, bending only
+    /// around obstacle corners where any cut enters the obstacle, so this is
+    /// a no-op for it. It only changes paths bent by other costs, such as
+    /// [`Self::with_clearance_weight`], or by a heuristic weight above 1.0.
+    pub fn with_smoothing(mut self, smoothing: bool) -> Self {
+        self.smoothing = smoothing;
+        self.recompute();
+        self
+    }
+
+    /// Rebuilds the search recording a history frame after each edge that
+    /// gives a vertex a cheaper path, marked as the frame's
+    /// [`SearchState::active_edge`], so playback follows every relaxation. Each frame gets its own step timing, covering the work up
+    /// to the next frame.
+    pub fn with_edge_granularity(mut self, edge_granularity: bool) -> Self {
+        self.edge_granularity = edge_granularity;
+        self.recompute();
+        self
+    }
+
+    /// Reruns the search as weighted A*, inflating the heuristic by `weight`.
+    /// A weight of 1.0 is plain A*; higher weights expand fewer nodes but may
+    /// return paths up to `weight` times longer than optimal.
+    pub fn set_weight(&mut self, weight: f32) {
+        self.weight = weight;
+        self.recompute();
+    }
+
+    /// Reruns the search for a new board, endpoints and heuristic, reusing
+    /// the visibility graph between obstacle vertices while the board stays
+    /// the same.
+    ///
+    /// Only the edges touching the start and goal are recomputed when they
+    /// move. The graph is built from scratch instead when the board changed,
+    /// when it was never built, or when the endpoints shape the graph beyond
+    /// their own edges, i.e. under orthogonal movement, taut pruning, or when
+    /// an endpoint sits on an obstacle vertex.
+    pub fn rebuild(&mut self, board: Board, start: Point, goal: Point, heuristic: Heuristic) {
+        let (old_start, old_goal) = (self.start, self.goal);
+        let board_changed = board != self.board;
+        self.board = board;
+        self.heuristic = heuristic;
+        if start != old_start || board_changed {
+            // A forked frontier only makes sense from the start it grew from
+            self.initial_state = None;
+        }
+
+        if (old_start, old_goal) != (start, goal) || board_changed {
+            let vertices = self.board.vertices_ordered();
+            let incremental = !board_changed
+                && self.graph_builds > 0
+                && self.movement == MovementModel::AnyAngle
+                && !self.prune_non_taut
+                && [old_start, old_goal, start, goal]
+                    .iter()
+                    .all(|point| !vertices.contains(point));
+
+            self.start = start;
+            self.goal = goal;
+
+            if incremental {
+                for old in [old_start, old_goal] {
+                    if old != start && old != goal {
+                        self.disconnect(old);
+                    }
+                }
+                for new in [start, goal] {
+                    if new != old_start && new != old_goal {
+                        self.connect(new, &vertices);
+                    }
+                }
+            } else {
+                self.rebuild_visibility_graph();
+            }
+        }
+
+        self.recompute();
+    }
+
+    /// Returns a copy of the search that resumes from the current step's open
+    /// and closed vertices instead of starting over, so that changing e.g.
+    /// the heuristic or weight afterwards continues from this frontier.
+    ///
+    /// The fork's history begins at the current step. Open vertices that
+    /// were also closed aren't expanded again.
+    pub fn fork_from_current_step(&self) -> Self {
+        let mut fork = self.clone();
+        fork.initial_state = Some(self.state.clone());
+        fork.recompute();
+        fork
+    }
+
+    /// Moves the start and reruns the search, recomputing only the start's
+    /// edges in the visibility graph where [`Self::rebuild`] allows it
+    pub fn move_start(&mut self, new_start: Point) {
+        self.rebuild(self.board.clone(), new_start, self.goal, self.heuristic);
+    }
 }
 
 impl Pathfinder for VisibilityGraphPathfinder {
     fn new(board: Board, start: Point, goal: Point, heuristic: Heuristic) -> Self {
-        let mut search = Self {
-            board,
-            start,
-            goal,
-            heuristic,
-            optimal_path: None,
-            visibility_graph: HashMap::new(),
-            state: SearchState {
-                open: HashSet::from([start]),
-                closed: HashSet::new(),
-                current_paths: HashMap::from([(start, vec![start])]),
-                best_path: None,
-                considered_edges: HashSet::new(),
-                next_vertex: Some(start),
-                g_scores: HashMap::from([(start, 0)]),
-                came_from: HashMap::new(),
-            },
-            current_step: 0,
-            history: Vec::new(),
-        };
-
-        // Build visibility graph and compute solution
-        search.visibility_graph = search.build_visibility_graph();
-        search.compute_optimal_path();
-        search.history.push(search.state.clone());
-        search.reset();
-
+        let mut search = Self::unsolved(board, start, goal, heuristic);
+        search.solve();
         search
     }
 
@@ -94,6 +344,10 @@ impl Pathfinder for VisibilityGraphPathfinder {
         self.optimal_path.as_ref()
     }
 
+    fn into_optimal_path(self) -> Option<(Vec<Point>, i32)> {
+        self.optimal_path
+    }
+
     fn total_steps(&self) -> usize {
         self.history.len() - 1
     }
@@ -129,6 +383,18 @@ impl Pathfinder for VisibilityGraphPathfinder {
         true
     }
 
+    fn goal_discovery_step(&self) -> Option<usize> {
+        self.goal_discovery_step
+    }
+
+    fn reopening_steps(&self) -> &[usize] {
+        &self.reopening_steps
+    }
+
+    fn step_timings(&self) -> &[Duration] {
+        &self.step_timings
+    }
+
     fn reset(&mut self) {
         self.current_step = 0;
         self.state = self.history[0].clone();
@@ -136,39 +402,211 @@ impl Pathfinder for VisibilityGraphPathfinder {
 
     fn change_heuristic(&mut self, heuristic: Heuristic) {
         self.heuristic = heuristic;
-        self.reset();
-        self.compute_optimal_path();
+        self.recompute();
+    }
+
+    fn status(&self) -> SearchStatus {
+        if self.is_finished() {
+            self.outcome
+        } else {
+            SearchStatus::Running
+        }
+    }
+
+    fn edge_cost(&self, from: &Point, to: &Point) -> i32 {
+        let distance = Self::distance(from, to);
+        let penalty = if self.clearance_weight > 0.0 {
+            // Segments off the graph, e.g. shortcuts when smoothing, are
+            // measured on the spot
+            let clearance = self
+                .clearances
+                .get(&(*from, *to))
+                .copied()
+                .unwrap_or_else(|| self.board.gap_clearance(from, to));
+            let weight = f64::from(self.clearance_weight);
+            (f64::from(distance) * weight / (1.0 + clearance)).round() as i32
+        } else {
+            0
+        };
+
+        distance + self.hop_penalty + penalty
     }
 }
 
 impl VisibilityGraphPathfinder {
+    /// A search with default settings that hasn't built its graph or run yet
+    fn unsolved(board: Board, start: Point, goal: Point, heuristic: Heuristic) -> Self {
+        Self {
+            board,
+            start,
+            goal,
+            heuristic,
+            hop_penalty: 0,
+            clearance_weight: 0.0,
+            clearances: HashMap::new(),
+            tie_break: TieBreak::default(),
+            movement: MovementModel::default(),
+            weight: 1.0,
+            prune_non_taut: false,
+            smoothing: false,
+            edge_granularity: false,
+            outcome: SearchStatus::Incomplete,
+            optimal_path: None,
+            goal_discovery_step: None,
+            reopening_steps: Vec::new(),
+            visibility_graph: HashMap::new(),
+            landmarks: None,
+            landmark_builds: 0,
+            graph_builds: 0,
+            state: SearchState::new(start),
+            initial_state: None,
+            current_step: 0,
+            history: Vec::new(),
+            step_timings: Vec::new(),
+        }
+    }
+
+    /// Builds the visibility graph, unless there's nowhere to go, and
+    /// computes the solution
+    fn solve(&mut self) {
+        if self.start != self.goal {
+            self.rebuild_visibility_graph();
+        }
+        self.recompute();
+    }
+
+    /// Discards any previous results and runs the search from scratch
+    fn recompute(&mut self) {
+        self.state = self
+            .initial_state
+            .clone()
+            .unwrap_or_else(|| SearchState::new(self.start));
+        self.optimal_path = None;
+        self.outcome = SearchStatus::Incomplete;
+        self.step_timings.clear();
+        if self
+            .landmarks
+            .as_ref()
+            .is_none_or(|(heuristic, _)| *heuristic != self.heuristic)
+        {
+            self.landmarks = self.select_landmarks();
+        }
+
+        // Already there: a single frame with the trivial path, no search needed
+        if self.start == self.goal {
+            self.state.best_path = Some(vec![self.start]);
+            self.optimal_path = Some((vec![self.start], 0));
+            self.outcome = SearchStatus::Found;
+            self.history = vec![self.state.clone()];
+        } else {
+            self.compute_optimal_path();
+            self.history.push(self.state.clone());
+            if self.smoothing {
+                self.smooth_optimal_path();
+            }
+        }
+
+        self.goal_discovery_step = goal_discovery_step(&self.history, &self.goal);
+        self.reopening_steps = reopening_steps(&self.history);
+        self.reset();
+    }
+
+    /// Picks the landmarks for [`Heuristic::Landmark`] among the graph's
+    /// vertices other than the endpoints, so they survive replanning
+    fn select_landmarks(&mut self) -> Option<(Heuristic, Landmarks)> {
+        let Heuristic::Landmark { count, strategy } = self.heuristic else {
+            return None;
+        };
+        self.landmark_builds += 1;
+        let candidates: Vec<Point> = self
+            .graph_vertices()
+            .into_iter()
+            .filter(|vertex| *vertex != self.start && *vertex != self.goal)
+            .collect();
+        let landmarks = Landmarks::new(&self.visibility_graph, &candidates, count, strategy);
+        Some((self.heuristic, landmarks))
+    }
+
+    /// The weighted heuristic estimate from `vertex` to the goal, raised to
+    /// the landmark bound where there is one
+    fn estimate(&self, vertex: &Point) -> i32 {
+        let distance = self.heuristic.distance(vertex, &self.goal);
+        let distance = match &self.landmarks {
+            Some((_, landmarks)) => distance.max(landmarks.estimate(vertex, &self.goal)),
+            None => distance,
+        };
+        if self.weight == 1.0 {
+            return distance;
+        }
+        (f64::from(distance) * f64::from(self.weight)) as i32
+    }
+
+    /// Replaces the optimal path with its [`smooth_path`] and reprices it,
+    /// unless the movement is orthogonal or smoothing would cross an
+    /// obstacle or make the path longer
+    fn smooth_optimal_path(&mut self) {
+        if self.movement == MovementModel::Orthogonal {
+            return;
+        }
+        let Some((path, _)) = &self.optimal_path else {
+            return;
+        };
+
+        let smoothed = smooth_path(&self.board, path);
+        if self.board.path_is_valid(&smoothed)
+            && Self::path_length_f64(&smoothed) <= Self::path_length_f64(path) + SMOOTHING_EPSILON
+        {
+            let cost = smoothed
+                .windows(2)
+                .map(|window| self.edge_cost(&window[0], &window[1]))
+                .sum();
+            self.optimal_path = Some((smoothed, cost));
+        }
+    }
+
     fn compute_optimal_path(&mut self) {
         self.history.clear();
         let mut open_set = BinaryHeap::new();
-
-        open_set.push(SearchNode {
-            vertex: self.start,
-            g_score: 0,
-            f_score: self.heuristic.distance(&self.start, &self.goal),
-        });
-        self.state.g_scores.insert(self.start, 0);
+        let mut sequence = 0;
+
+        let frontier = match &self.initial_state {
+            Some(state) => state
+                .open_sorted()
+                .into_iter()
+                .filter(|vertex| !state.closed.contains(vertex))
+                .collect(),
+            None => vec![self.start],
+        };
+        for (i, vertex) in frontier.into_iter().enumerate() {
+            let g_score = self.state.g_scores.get(&vertex).copied().unwrap_or(0);
+            sequence = i;
+            open_set.push(SearchNode {
+                vertex,
+                g_score,
+                f_score: g_score + self.estimate(&vertex),
+                sequence,
+                tie_break: self.tie_break,
+            });
+        }
 
         while let Some(current) = open_set.pop() {
             if current.vertex == self.goal {
                 let path = self.reconstruct_path(&current.vertex);
                 self.optimal_path = Some((path.clone(), current.g_score));
                 self.state.best_path = Some(path);
+                self.outcome = SearchStatus::Found;
                 return;
             }
 
             // Save state for visualization
             self.history.push(self.state.clone());
-            self.state.closed.insert(current.vertex);
+            self.state.close(current.vertex);
+            let mut frame_start = Instant::now();
 
             if let Some(neighbors) = self.visibility_graph.get(&current.vertex) {
                 for &neighbor in neighbors {
                     let tentative_g_score =
-                        current.g_score + Self::distance(&current.vertex, &neighbor);
+                        current.g_score + self.edge_cost(&current.vertex, &neighbor);
 
                     if !self.state.g_scores.contains_key(&neighbor)
                         || tentative_g_score < *self.state.g_scores.get(&neighbor).unwrap()
@@ -179,32 +617,150 @@ impl VisibilityGraphPathfinder {
                         let mut new_path = self.reconstruct_path(&current.vertex);
                         new_path.push(neighbor);
                         self.state.current_paths.insert(neighbor, new_path);
-                        self.state
-                            .considered_edges
-                            .insert((current.vertex, neighbor));
+                        self.state.consider(current.vertex, neighbor);
 
+                        sequence += 1;
                         open_set.push(SearchNode {
                             vertex: neighbor,
                             g_score: tentative_g_score,
-                            f_score: tentative_g_score
-                                + self.heuristic.distance(&neighbor, &self.goal),
+                            f_score: tentative_g_score + self.estimate(&neighbor),
+                            sequence,
+                            tie_break: self.tie_break,
                         });
                         self.state.open.insert(neighbor);
+
+                        if self.edge_granularity {
+                            // The previous frame's work ends where this one starts
+                            self.step_timings.push(frame_start.elapsed());
+                            frame_start = Instant::now();
+                            self.state.active_edge = Some((current.vertex, neighbor));
+                            self.history.push(self.state.clone());
+                        }
                     }
                 }
             }
+            self.state.active_edge = None;
+            self.step_timings.push(frame_start.elapsed());
         }
+
+        // Open set exhausted without reaching the goal
+        self.outcome = SearchStatus::NoPath;
+    }
+
+    /// Returns the sightlines from `vertex` to every other vertex the
+    /// visibility graph is built over, whether or not they are visible
+    pub fn sightlines(&self, vertex: Point) -> Vec<Sightline> {
+        self.graph_vertices()
+            .into_iter()
+            .filter(|&other| other != vertex)
+            .map(|other| {
+                let blocked_at = (!self.board.vertices_visible(&vertex, &other))
+                    .then(|| self.blocking_point(vertex, other));
+                Sightline {
+                    to: other,
+                    blocked_at,
+                }
+            })
+            .collect()
     }
 
-    /// Builds visibility graph based on inter-visible vertices
+    /// The point nearest `from` where the segment to `to` meets the boundary
+    /// of a polygon blocking it, which is `from` itself for a vertex of that
+    /// polygon, falling back to `to`
+    fn blocking_point(&self, from: Point, to: Point) -> Point {
+        let sightline = Edge::new(from, to);
+        self.board
+            .enabled_polygons()
+            .filter(|polygon| polygon.intersects_segment(&from, &to))
+            .flat_map(|polygon| polygon.edges_iter())
+            .filter_map(|edge| sightline.intersection_point(&edge))
+            .min_by_key(|p| {
+                let (dx, dy) = ((p.x - from.x) as i64, (p.y - from.y) as i64);
+                dx * dx + dy * dy
+            })
+            .unwrap_or(to)
+    }
+
+    fn rebuild_visibility_graph(&mut self) {
+        self.visibility_graph = self.build_visibility_graph();
+        self.graph_builds += 1;
+        self.landmarks = None;
+        self.clearances.clear();
+        self.measure_clearances();
+    }
+
+    /// Measures the clearance of every graph edge not measured yet, if edge
+    /// costs depend on it, so relaxing an edge doesn't sample it again
+    fn measure_clearances(&mut self) {
+        if self.clearance_weight == 0.0 {
+            return;
+        }
+        for (&from, neighbors) in &self.visibility_graph {
+            for &to in neighbors {
+                if !self.clearances.contains_key(&(from, to)) {
+                    let clearance = self.board.gap_clearance(&from, &to);
+                    self.clearances.insert((from, to), clearance);
+                    self.clearances.insert((to, from), clearance);
+                }
+            }
+        }
+    }
+
+    /// Removes a point and all of its edges from the visibility graph
+    fn disconnect(&mut self, point: Point) {
+        self.landmarks = None;
+        self.clearances
+            .retain(|&(from, to), _| from != point && to != point);
+        for neighbor in self.visibility_graph.remove(&point).unwrap_or_default() {
+            if let Some(edges) = self.visibility_graph.get_mut(&neighbor) {
+                edges.remove(&point);
+                if edges.is_empty() {
+                    self.visibility_graph.remove(&neighbor);
+                }
+            }
+        }
+    }
+
+    /// Adds edges between a point and every obstacle vertex or endpoint it can
+    /// see
+    fn connect(&mut self, point: Point, vertices: &[Point]) {
+        self.landmarks = None;
+        let others = vertices.iter().chain([&self.start, &self.goal]);
+        let visible: Vec<Point> = others
+            .filter(|&&other| other != point && self.board.vertices_visible(&point, &other))
+            .copied()
+            .collect();
+
+        for other in visible {
+            self.visibility_graph
+                .entry(point)
+                .or_default()
+                .insert(other);
+            self.visibility_graph
+                .entry(other)
+                .or_default()
+                .insert(point);
+        }
+        self.measure_clearances();
+    }
+
+    /// The waypoints allowed by the [`MovementModel`] plus the start, in a
+    /// deterministic order so graph construction doesn't depend on hashing
+    fn graph_vertices(&self) -> Vec<Point> {
+        let mut vertices = self.movement.waypoints(&self.board, self.start, self.goal);
+
+        // Add start to vertices, since waypoints already include the goal
+        if !vertices.contains(&self.start) {
+            vertices.push(self.start);
+        }
+        vertices
+    }
+
+    /// Builds visibility graph based on inter-visible waypoints allowed by
+    /// the [`MovementModel`]
     fn build_visibility_graph(&self) -> HashMap<Point, HashSet<Point>> {
         let mut graph: HashMap<Point, HashSet<Point>> = HashMap::new();
-        let mut vertices = self.board.vertices();
-
-        // Add start and goal to vertices
-        vertices.insert(self.start);
-        vertices.insert(self.goal);
-        let vertices: Vec<_> = vertices.into_iter().collect();
+        let vertices = self.graph_vertices();
 
         for (i, &v1) in vertices.iter().enumerate() {
             for (j, &v2) in vertices.iter().enumerate() {
@@ -212,7 +768,11 @@ impl VisibilityGraphPathfinder {
                     continue;
                 }
 
-                if self.are_vertices_visible(v1, v2) {
+                if self.movement.allows(&v1, &v2)
+                    && self.board.vertices_visible(&v1, &v2)
+                    && self.is_taut(v1, v2)
+                    && self.is_taut(v2, v1)
+                {
                     graph.entry(v1).or_default().insert(v2);
                     graph.entry(v2).or_default().insert(v1);
                 }
@@ -222,47 +782,49 @@ impl VisibilityGraphPathfinder {
         graph
     }
 
-    /// Determines if two vertices can see each other
-    fn are_vertices_visible(&self, v1: Point, v2: Point) -> bool {
-        if v1 == v2 {
-            return false;
+    /// Determines if a path arriving at `vertex` from `other` could wrap
+    /// tightly around every polygon corner at `vertex`, i.e. both of the
+    /// corner's neighbors lie on the same side of the line through the two
+    /// points. Always true unless pruning is enabled for any-angle movement.
+    fn is_taut(&self, vertex: Point, other: Point) -> bool {
+        if !self.prune_non_taut
+            || self.movement != MovementModel::AnyAngle
+            || vertex == self.start
+            || vertex == self.goal
+        {
+            return true;
         }
 
-        for polygon in self.board.polygons() {
-            // Special case: if both points are vertices of same polygon
-            let v1_in_polygon = polygon.vertices_vec().contains(&v1);
-            let v2_in_polygon = polygon.vertices_vec().contains(&v2);
-
-            if v1_in_polygon && v2_in_polygon {
-                // Visible if they're adjacent vertices
-                let vertices = polygon.vertices_vec();
-                let n = vertices.len();
-                for i in 0..n {
-                    let j = (i + 1) % n;
-                    if (vertices[i] == v1 && vertices[j] == v2)
-                        || (vertices[i] == v2 && vertices[j] == v1)
-                    {
-                        return true;
-                    }
-                }
-                // Non-adjacent vertices of same polygon can't see each other
-                return false;
-            }
+        // Positive when `p` lies to the left of the line from `other` to `vertex`
+        let side = |p: &Point| {
+            let (dx, dy) = ((vertex.x - other.x) as i64, (vertex.y - other.y) as i64);
+            let (px, py) = ((p.x - other.x) as i64, (p.y - other.y) as i64);
+            (dx * py - dy * px).signum()
+        };
 
-            // Check if line segment intersects this polygon
-            if polygon.intersects_segment(&v1, &v2) {
-                return false;
-            }
-        }
+        self.board.enabled_polygons().all(|polygon| {
+            let vertices = polygon.vertices_vec();
+            let n = vertices.len();
 
-        true
+            vertices.iter().enumerate().all(|(i, v)| {
+                if *v != vertex {
+                    return true;
+                }
+                let prev = side(&vertices[(i + n - 1) % n]);
+                let next = side(&vertices[(i + 1) % n]);
+                prev * next >= 0
+            })
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Polygon;
+    use crate::board::sample_board;
+    use crate::search::shortest_path_cost;
+    use crate::visibility_graph_to_json;
+    use crate::{LandmarkStrategy, Polygon};
 
     // Helper function to create a simple test board with one obstacle
     fn create_test_board() -> Board {
@@ -275,6 +837,46 @@ mod tests {
         Board::new(polygons)
     }
 
+    #[test]
+    fn test_sightlines_match_graph() {
+        let board = create_test_board();
+        let (start, goal) = (Point::new(0, 0), Point::new(100, 100));
+        let search = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean);
+        let corner = Point::new(40, 40);
+        let sightlines = search.sightlines(corner);
+
+        let visible: HashSet<Point> = sightlines
+            .iter()
+            .filter(|s| s.blocked_at.is_none())
+            .map(|s| s.to)
+            .collect();
+        let expected: HashSet<Point> = [start, Point::new(40, 60), Point::new(60, 40)].into();
+        assert_eq!(visible, expected);
+        assert_eq!(visible, search.visibility_graph[&corner]);
+
+        // The diagonal to the goal runs straight into the corner's own square
+        let to_goal = sightlines.iter().find(|s| s.to == goal).unwrap();
+        assert_eq!(to_goal.blocked_at, Some(corner));
+
+        // From the start, it's blocked where it first meets the square rather
+        // than where it leaves it
+        let from_start = search.sightlines(start);
+        let to_goal = from_start.iter().find(|s| s.to == goal).unwrap();
+        assert_eq!(to_goal.blocked_at, Some(corner));
+    }
+
+    #[test]
+    fn test_optimal_path_cloned() {
+        let board = create_test_board();
+        let (start, goal) = (Point::new(0, 0), Point::new(100, 100));
+        let search = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean);
+
+        let cloned = search.optimal_path_cloned();
+        assert!(cloned.is_some());
+        assert_eq!(cloned.as_ref(), search.get_optimal_path());
+        assert_eq!(search.into_optimal_path(), cloned);
+    }
+
     #[test]
     fn test_search_completes() {
         let board = create_test_board();
@@ -345,19 +947,11 @@ mod tests {
         let (path, _) = search.get_optimal_path().unwrap();
 
         // Check that no line segment in the path intersects with any polygon
-        for window in path.windows(2) {
-            let from = window[0];
-            let to = window[1];
-
-            for polygon in board.polygons() {
-                assert!(
-                    !polygon.intersects_segment(&from, &to),
-                    "Path segment from {:?} to {:?} intersects with polygon",
-                    from,
-                    to
-                );
-            }
-        }
+        assert!(
+            board.path_is_valid(path),
+            "Path {:?} intersects with polygon",
+            path
+        );
     }
 
     #[test]
@@ -446,4 +1040,614 @@ mod tests {
             "Best path should reach goal in final state"
         );
     }
+
+    #[test]
+    fn test_hop_penalty_prefers_fewer_vertices() {
+        // Going over the top takes three hops and is slightly shorter than
+        // going around the bottom tip, which takes only two
+        let board = Board::new(vec![Polygon::new(vec![
+            (40, 50).into(),
+            (60, 50).into(),
+            (60, 0).into(),
+            (50, -58).into(),
+            (40, 0).into(),
+        ])]);
+
+        let start = Point::new(0, 0);
+        let goal = Point::new(100, 0);
+
+        let plain =
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let penalized = VisibilityGraphPathfinder::new_with_hop_penalty(
+            board,
+            start,
+            goal,
+            Heuristic::Euclidean,
+            10,
+        );
+
+        let (plain_path, _) = plain.get_optimal_path().unwrap();
+        let (penalized_path, _) = penalized.get_optimal_path().unwrap();
+
+        assert_eq!(
+            plain_path.len(),
+            4,
+            "Unpenalized path should go over the top"
+        );
+        assert_eq!(
+            penalized_path.len(),
+            3,
+            "Penalized path should go around the bottom tip"
+        );
+        assert!(
+            penalized_path.len() < plain_path.len(),
+            "Hop penalty should favor paths with fewer vertices"
+        );
+    }
+
+    #[test]
+    fn test_orthogonal_movement() {
+        let board = create_test_board();
+        let start = Point::new(0, 50);
+        let goal = Point::new(100, 50);
+        let search =
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Manhattan)
+                .with_movement(MovementModel::Orthogonal);
+
+        let (path, _) = search.get_optimal_path().unwrap();
+        assert!(board.path_is_valid(path), "Path {:?} is invalid", path);
+        for window in path.windows(2) {
+            assert!(
+                window[0].x == window[1].x || window[0].y == window[1].y,
+                "Segment {:?} -> {:?} is not axis-aligned",
+                window[0],
+                window[1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_edge_granularity() {
+        // A board small enough that no vertex is expanded twice
+        let board = create_test_board();
+        let (start, goal) = (Point::new(0, 50), Point::new(100, 40));
+        let by_node =
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let by_edge = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean)
+            .with_edge_granularity(true);
+
+        // One frame per relaxed edge, each showing the edge it relaxed
+        let last = by_edge.history().last().unwrap();
+        let considered = last.considered_edges.len();
+        let edge_frames: Vec<&SearchState> = by_edge
+            .history()
+            .iter()
+            .filter(|state| state.active_edge.is_some())
+            .collect();
+        assert_eq!(edge_frames.len(), considered);
+        for state in edge_frames {
+            assert!(state.considered_edges.contains(&state.active_edge.unwrap()));
+        }
+        assert_eq!(
+            by_edge.history().len(),
+            considered + last.expansion_order.len() + 1
+        );
+
+        // Timings line up with frames in either mode
+        for search in [&by_node, &by_edge] {
+            assert_eq!(search.step_timings().len(), search.history().len() - 1);
+        }
+        assert!(by_node
+            .history()
+            .iter()
+            .all(|state| state.active_edge.is_none()));
+        assert_eq!(by_edge.optimal_path_score(), by_node.optimal_path_score());
+    }
+
+    #[test]
+    fn test_expansion_order() {
+        let board = sample_board();
+        let (start, goal) = board.default_endpoints();
+        let search = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean);
+        let state = search.history().last().unwrap();
+
+        assert_eq!(state.expansion_order.get(&start), Some(&0));
+        assert_eq!(
+            state
+                .expansion_order
+                .keys()
+                .copied()
+                .collect::<HashSet<_>>(),
+            state.closed
+        );
+        let mut orders: Vec<usize> = state.expansion_order.values().copied().collect();
+        orders.sort_unstable();
+        assert_eq!(orders, (0..state.closed.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_recent_edges() {
+        let board = sample_board();
+        let (start, goal) = board.default_endpoints();
+        let search = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean);
+
+        for pair in search.history().windows(2) {
+            let (before, after) = (&pair[0], &pair[1]);
+            if after.expansion_order.len() == before.expansion_order.len() {
+                continue;
+            }
+            let recent: HashSet<_> = after.recent_edges(1).into_iter().collect();
+            let added: HashSet<_> = after
+                .considered_edges
+                .difference(&before.considered_edges)
+                .copied()
+                .collect();
+            assert!(added.is_subset(&recent));
+            assert!(recent
+                .iter()
+                .all(|(from, _)| after.expansion_order[from] == after.expansion_order.len() - 1));
+        }
+
+        let last = search.history().last().unwrap();
+        assert_eq!(
+            last.recent_edges(last.expansion_order.len() + 1).len(),
+            last.considered_edges.len()
+        );
+    }
+
+    #[test]
+    fn test_landmarks_tighten_estimate() {
+        let board = sample_board();
+        let (start, goal) = (Point::new(115, 655), Point::new(380, 560));
+        let landmarks = |count| {
+            let heuristic = Heuristic::landmarks(count, LandmarkStrategy::Farthest);
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, heuristic)
+        };
+        let euclidean =
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let (one, four) = (landmarks(1), landmarks(4));
+
+        // Admissible, so still optimal
+        assert_eq!(one.optimal_path_score(), euclidean.optimal_path_score());
+        assert_eq!(four.optimal_path_score(), euclidean.optimal_path_score());
+
+        // A lone landmark at the goal knows the exact cost to it
+        let graph = &four.visibility_graph;
+        let exact = Landmarks::new(graph, &[goal], 1, LandmarkStrategy::Farthest);
+        let (_, bound) = four.landmarks.as_ref().unwrap();
+        for vertex in graph.keys() {
+            assert!(
+                bound.estimate(vertex, &goal) <= exact.estimate(vertex, &goal),
+                "Overestimates at {vertex:?}"
+            );
+        }
+
+        assert!(
+            four.total_steps() <= one.total_steps(),
+            "More landmarks shouldn't expand more ({} vs {})",
+            four.total_steps(),
+            one.total_steps()
+        );
+        assert!(one.total_steps() <= euclidean.total_steps());
+    }
+
+    #[test]
+    fn test_landmarks_cached_until_graph_changes() {
+        let (start, goal) = (Point::new(115, 655), Point::new(380, 560));
+        let heuristic = Heuristic::landmarks(4, LandmarkStrategy::Farthest);
+        let mut search = VisibilityGraphPathfinder::new(sample_board(), start, goal, heuristic);
+        assert_eq!(search.landmark_builds, 1);
+
+        search.set_weight(2.0);
+        search.change_heuristic(heuristic);
+        assert_eq!(search.landmark_builds, 1, "Same graph, same landmarks");
+
+        search.change_heuristic(Heuristic::landmarks(2, LandmarkStrategy::Farthest));
+        assert_eq!(search.landmark_builds, 2, "Different landmark settings");
+
+        search.move_start(Point::new(120, 650));
+        assert_eq!(
+            search.landmark_builds, 3,
+            "Moving the start changes the graph"
+        );
+    }
+
+    #[test]
+    fn test_taut_pruning_keeps_optimal_cost() {
+        let board = sample_board();
+        let start = Point::new(115, 655);
+        let goal = Point::new(380, 560);
+
+        let full = VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let pruned = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean)
+            .with_taut_pruning(true);
+
+        let edges = |search: &VisibilityGraphPathfinder| -> usize {
+            search.visibility_graph.values().map(HashSet::len).sum()
+        };
+        assert!(
+            edges(&pruned) < edges(&full),
+            "Pruning should drop edges ({} vs {})",
+            edges(&pruned),
+            edges(&full)
+        );
+        assert_eq!(pruned.optimal_path_score(), full.optimal_path_score());
+    }
+
+    #[test]
+    fn test_new_with_options() {
+        let board = sample_board();
+        let (start, goal) = board.default_endpoints();
+        let options = SearchOptions {
+            weight: 1.5,
+            reduced_graph: true,
+            edge_granularity: true,
+        };
+        let search = VisibilityGraphPathfinder::new_with_options(
+            board.clone(),
+            start,
+            goal,
+            Heuristic::Euclidean,
+            options,
+        );
+        assert_eq!(search.graph_builds, 1, "The graph is built once");
+
+        let chained = VisibilityGraphPathfinder::new_with_weight(
+            board,
+            start,
+            goal,
+            Heuristic::Euclidean,
+            options.weight,
+        )
+        .with_taut_pruning(true)
+        .with_edge_granularity(true);
+        assert_eq!(search.visibility_graph, chained.visibility_graph);
+        assert_eq!(search.get_optimal_path(), chained.get_optimal_path());
+        assert!(search.history.iter().any(|s| s.active_edge.is_some()));
+    }
+
+    #[test]
+    fn test_rebuild_reuses_visibility_graph() {
+        let board = create_test_board();
+        let start = Point::new(0, 0);
+        let goal = Point::new(100, 100);
+        let mut search = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean);
+        assert_eq!(search.graph_builds, 1);
+
+        search.rebuild(create_test_board(), start, goal, Heuristic::Manhattan);
+        assert_eq!(
+            search.graph_builds, 1,
+            "Heuristic change should keep the graph"
+        );
+        assert_eq!(search.get_heuristic(), Heuristic::Manhattan);
+
+        let moved = Point::new(10, 90);
+        search.rebuild(create_test_board(), moved, goal, Heuristic::Manhattan);
+        assert_eq!(
+            search.graph_builds, 1,
+            "Moving an endpoint should patch the graph"
+        );
+        assert_eq!(search.visibility_graph, search.build_visibility_graph());
+        assert_eq!(
+            search.optimal_path_score(),
+            VisibilityGraphPathfinder::new(create_test_board(), moved, goal, Heuristic::Manhattan)
+                .optimal_path_score()
+        );
+
+        let open = Board::new(Vec::new());
+        search.rebuild(open.clone(), moved, goal, Heuristic::Manhattan);
+        assert_eq!(search.graph_builds, 2, "A new board needs a new graph");
+        assert_eq!(search.visibility_graph, search.build_visibility_graph());
+        assert_eq!(
+            search.get_optimal_path(),
+            VisibilityGraphPathfinder::new(open, moved, goal, Heuristic::Manhattan)
+                .get_optimal_path()
+        );
+    }
+
+    #[test]
+    fn test_clearance_weight() {
+        // Two diamonds whose tips leave a narrow gap on the straight line
+        let diamond = |x: i32, y: i32| {
+            Polygon::new(vec![
+                (x, y - 23).into(),
+                (x + 20, y).into(),
+                (x, y + 23).into(),
+                (x - 20, y).into(),
+            ])
+        };
+        let board = Board::new(vec![diamond(50, 75), diamond(50, 25)]);
+        let (start, goal) = (Point::new(0, 50), Point::new(100, 50));
+        let min_clearance = |search: &VisibilityGraphPathfinder| {
+            let (path, _) = search.get_optimal_path().unwrap();
+            path.windows(2)
+                .map(|w| board.gap_clearance(&w[0], &w[1]))
+                .fold(f64::INFINITY, f64::min)
+        };
+
+        let shortest =
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let safe = VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean)
+            .with_clearance_weight(20.0);
+
+        assert_eq!(shortest.get_optimal_path().unwrap().0, vec![start, goal]);
+        assert!(
+            min_clearance(&safe) > min_clearance(&shortest),
+            "{:?}",
+            safe.get_optimal_path()
+        );
+        assert!(board.path_is_valid(&safe.get_optimal_path().unwrap().0));
+        let length = |search: &VisibilityGraphPathfinder| {
+            VisibilityGraphPathfinder::path_length_f64(&search.get_optimal_path().unwrap().0)
+        };
+        assert!(length(&safe) > length(&shortest), "Safety costs length");
+
+        // Clearances are measured once per graph edge, and follow the graph
+        // when an endpoint moves
+        let mut safe = safe;
+        safe.move_start(Point::new(0, 40));
+        let edges: usize = safe.visibility_graph.values().map(HashSet::len).sum();
+        assert_eq!(safe.clearances.len(), edges);
+        for (from, neighbors) in &safe.visibility_graph {
+            for to in neighbors {
+                assert_eq!(
+                    safe.clearances[&(*from, *to)],
+                    board.gap_clearance(from, to)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_disabled_polygon() {
+        let (start, goal) = (Point::new(0, 50), Point::new(100, 50));
+        let mut board = create_test_board();
+        board.set_polygon_enabled(0, false);
+
+        let search =
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        assert_eq!(search.get_optimal_path(), Some(&(vec![start, goal], 100)));
+
+        // Still on the board, just no longer in the way
+        assert_eq!(board.polygons().count(), 1);
+        assert_eq!(board.polygon_at(&Point::new(50, 50)), Some(0));
+
+        board.set_polygon_enabled(0, true);
+        let search = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean);
+        assert!(search.optimal_path_score().unwrap() > 100);
+    }
+
+    #[test]
+    fn test_move_start() {
+        let goal = Point::new(100, 100);
+        let mut search = VisibilityGraphPathfinder::new(
+            create_test_board(),
+            Point::new(0, 0),
+            goal,
+            Heuristic::Euclidean,
+        );
+
+        for start in [Point::new(10, 90), Point::new(30, 0)] {
+            search.move_start(start);
+            let fresh = VisibilityGraphPathfinder::new(
+                create_test_board(),
+                start,
+                goal,
+                Heuristic::Euclidean,
+            );
+
+            assert_eq!(search.get_start(), start);
+            assert_eq!(search.visibility_graph, fresh.visibility_graph);
+            assert_eq!(search.get_optimal_path(), fresh.get_optimal_path());
+            assert_eq!(search.graph_builds, 1);
+        }
+    }
+
+    #[test]
+    fn test_start_equals_goal() {
+        let point = Point::new(10, 10);
+        let mut search =
+            VisibilityGraphPathfinder::new(create_test_board(), point, point, Heuristic::Euclidean);
+
+        assert_eq!(search.get_optimal_path(), Some(&(vec![point], 0)));
+        assert_eq!(search.total_steps(), 0);
+        assert_eq!(search.graph_builds, 0, "No graph is needed to stay put");
+
+        // Moving the goal away later still builds a full graph
+        search.rebuild(
+            create_test_board(),
+            point,
+            Point::new(100, 100),
+            Heuristic::Euclidean,
+        );
+        assert_eq!(search.visibility_graph, search.build_visibility_graph());
+        assert!(search.get_optimal_path().is_some());
+    }
+
+    #[test]
+    fn test_endpoints_on_obstacle_vertices() {
+        let board = create_test_board();
+        let (start, goal) = (Point::new(100, 0), Point::new(40, 60));
+        let search =
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+
+        let (path, cost) = search
+            .get_optimal_path()
+            .expect("Goal on a corner is reachable");
+        assert!(board.path_is_valid(path));
+        assert_eq!(Some(*cost), shortest_path_cost(&board, start, goal));
+
+        // Both endpoints on an L-shaped obstacle, seeing each other across
+        // its notch rather than only along its sides
+        let board = Board::new(vec![Polygon::new(vec![
+            (0, 0).into(),
+            (0, 20).into(),
+            (10, 20).into(),
+            (10, 10).into(),
+            (20, 10).into(),
+            (20, 0).into(),
+        ])]);
+        let (start, goal) = (Point::new(10, 20), Point::new(20, 10));
+        let search = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean);
+        assert_eq!(search.get_optimal_path(), Some(&(vec![start, goal], 14)));
+    }
+
+    #[test]
+    fn test_goal_discovery_step() {
+        let goal = Point::new(100, 100);
+        let search = VisibilityGraphPathfinder::new(
+            create_test_board(),
+            Point::new(0, 0),
+            goal,
+            Heuristic::Euclidean,
+        );
+
+        let step = search
+            .goal_discovery_step()
+            .expect("The goal should be discovered");
+        assert!(search.history()[step].open.contains(&goal));
+        assert!(search.history()[..step]
+            .iter()
+            .all(|state| !state.open.contains(&goal)));
+    }
+
+    #[test]
+    fn test_status_found() {
+        let board = create_test_board();
+        let start = Point::new(0, 0);
+        let goal = Point::new(100, 100);
+        let mut search = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean);
+
+        assert_eq!(search.status(), SearchStatus::Running);
+        search.jump_to(search.total_steps());
+        assert_eq!(search.status(), SearchStatus::Found);
+    }
+
+    #[test]
+    fn test_detour_factor() {
+        let search = |start, goal| {
+            VisibilityGraphPathfinder::new(create_test_board(), start, goal, Heuristic::Euclidean)
+        };
+
+        let around = search(Point::new(0, 50), Point::new(100, 50));
+        let detour = around.detour_factor().unwrap();
+        assert!(detour >= 1.0);
+        // Around a corner: 2 * hypot(40, 10) + 20 over 100
+        assert!((detour - (2.0 * 40.0_f64.hypot(10.0) + 20.0) / 100.0).abs() < 1e-9);
+
+        let visible = search(Point::new(0, 0), Point::new(100, 0));
+        assert!((visible.detour_factor().unwrap() - 1.0).abs() < 1e-9);
+
+        let unreachable = search(Point::new(0, 0), Point::new(50, 50));
+        assert_eq!(unreachable.detour_factor(), None);
+    }
+
+    #[test]
+    fn test_visibility_graph_to_json() {
+        let search = VisibilityGraphPathfinder::new(
+            create_test_board(),
+            Point::new(0, 50),
+            Point::new(100, 50),
+            Heuristic::Euclidean,
+        );
+        let graph = search.visibility_graph();
+        let json: serde_json::Value =
+            serde_json::from_str(&visibility_graph_to_json(graph)).unwrap();
+
+        // The square's four corners plus the start and goal
+        let nodes = json["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 6);
+        assert_eq!(nodes[0], serde_json::json!([0, 50]));
+
+        let edges = json["edges"].as_array().unwrap();
+        let directed: usize = graph.values().map(HashSet::len).sum();
+        assert_eq!(edges.len() * 2, directed, "Symmetric edges listed once");
+        assert!(edges.iter().all(|edge| {
+            let (i, j) = (edge[0].as_u64().unwrap(), edge[1].as_u64().unwrap());
+            i < j && (j as usize) < nodes.len()
+        }));
+    }
+
+    #[test]
+    fn test_step_timings() {
+        let search = VisibilityGraphPathfinder::new(
+            create_test_board(),
+            Point::new(0, 50),
+            Point::new(100, 50),
+            Heuristic::Euclidean,
+        );
+
+        // One frame per expansion, plus the final one
+        let expansions = search.history().len() - 1;
+        assert!(expansions > 0);
+        assert_eq!(search.step_timings().len(), expansions);
+        assert!(search.step_timings().iter().all(|&d| d >= Duration::ZERO));
+
+        let trivial = VisibilityGraphPathfinder::new(
+            create_test_board(),
+            Point::new(0, 50),
+            Point::new(0, 50),
+            Heuristic::Euclidean,
+        );
+        assert!(trivial.step_timings().is_empty());
+    }
+
+    #[test]
+    fn test_smoothing() {
+        let board = sample_board();
+        let (start, goal) = (Point::new(0, 0), Point::new(280, 630));
+
+        // A plain shortest path is taut, leaving no corner to cut
+        let shortest =
+            VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean);
+        let raw = shortest.get_optimal_path().unwrap().clone();
+        assert_eq!(shortest.with_smoothing(true).get_optimal_path(), Some(&raw));
+
+        // Keeping clear of obstacles bends the path away from them instead
+        let raw = VisibilityGraphPathfinder::new(board.clone(), start, goal, Heuristic::Euclidean)
+            .with_clearance_weight(5.0);
+        let smoothed = raw.clone().with_smoothing(true);
+        let (raw_path, raw_cost) = raw.get_optimal_path().unwrap();
+        let (path, cost) = smoothed.get_optimal_path().unwrap();
+
+        assert_ne!(path, raw_path, "Smoothing should cut a corner");
+        assert!(board.path_is_valid(path), "Path {:?} is invalid", path);
+        assert!(
+            VisibilityGraphPathfinder::path_length_f64(path)
+                <= VisibilityGraphPathfinder::path_length_f64(raw_path) + SMOOTHING_EPSILON
+        );
+        let repriced: i32 = path
+            .windows(2)
+            .map(|w| smoothed.edge_cost(&w[0], &w[1]))
+            .sum();
+        assert_eq!(*cost, repriced, "Cost should match the smoothed path");
+        assert!(cost < raw_cost);
+
+        // Cutting an orthogonal path's corners would leave the grid
+        let board = create_test_board();
+        let orthogonal = VisibilityGraphPathfinder::new(
+            board.clone(),
+            Point::new(0, 0),
+            Point::new(100, 100),
+            Heuristic::Manhattan,
+        )
+        .with_movement(MovementModel::Orthogonal);
+        let raw = orthogonal.get_optimal_path().unwrap().clone();
+        let smoothed = orthogonal.with_smoothing(true);
+
+        assert_eq!(smoothed.get_optimal_path(), Some(&raw));
+    }
+
+    #[test]
+    fn test_status_no_path() {
+        // The goal sits inside the obstacle, so it can never be reached
+        let board = create_test_board();
+        let start = Point::new(0, 0);
+        let goal = Point::new(50, 50);
+        let mut search = VisibilityGraphPathfinder::new(board, start, goal, Heuristic::Euclidean);
+
+        search.jump_to(search.total_steps());
+        assert_eq!(search.status(), SearchStatus::NoPath);
+        assert!(search.get_optimal_path().is_none());
+    }
 }
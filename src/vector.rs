@@ -41,6 +41,31 @@ impl<T> Vector<T> {
 impl Vector {
     /// The zero [`Vector`].
     pub const ZERO: Self = Self::new(0.0, 0.0);
+
+    /// Reflects the [`Vector`] off a surface with the given normal, which
+    /// need not be normalized.
+    pub fn reflect(self, normal: Self) -> Self {
+        let length_squared = normal.x * normal.x + normal.y * normal.y;
+        if length_squared == 0.0 {
+            return self;
+        }
+
+        let dot = self.x * normal.x + self.y * normal.y;
+        self - normal * (2.0 * dot / length_squared)
+    }
+
+    /// Returns the z component of the cross product, positive when `other`
+    /// lies counterclockwise of the [`Vector`].
+    pub fn cross(self, other: Self) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns the unsigned angle to another [`Vector`] in radians, between
+    /// 0 and π, or 0 if either is zero.
+    pub fn angle_between(self, other: Self) -> f32 {
+        let dot = self.x * other.x + self.y * other.y;
+        self.cross(other).abs().atan2(dot)
+    }
 }
 
 impl<T> std::ops::Neg for Vector<T>